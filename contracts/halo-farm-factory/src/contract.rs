@@ -0,0 +1,135 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+};
+use cw2::set_contract_version;
+use cw_utils::parse_reply_instantiate_data;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:halo-farm-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Reply id of the farm instantiation submessage
+pub const INSTANTIATE_FARM_REPLY_ID: u64 = 1;
+
+use crate::{
+    error::ContractError,
+    execute::{
+        execute_create_farm, execute_sync_farm_owner, execute_update_config, tokens_to_string,
+    },
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{
+        query_config, query_farm, query_farms, query_farms_by_owner, query_farms_by_staked_token,
+    },
+    state::{farms, Config, FarmInfo, CONFIG, PENDING_FARM_INFO},
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        owner: info.sender,
+        farm_code_id: msg.farm_code_id,
+    };
+
+    // Save config
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "instantiate"),
+        ("owner", config.owner.as_ref()),
+        ("farm_code_id", &config.farm_code_id.to_string()),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            owner,
+            farm_code_id,
+        } => execute_update_config(deps, info, owner, farm_code_id),
+        ExecuteMsg::CreateFarm { create_farm_msg } => {
+            execute_create_farm(deps, env, info, create_farm_msg)
+        }
+        ExecuteMsg::SyncFarmOwner { farm } => execute_sync_farm_owner(deps, farm),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != INSTANTIATE_FARM_REPLY_ID {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Unknown reply id: {}",
+            msg.id
+        ))));
+    }
+
+    // Get the address of the new farm contract
+    let res = parse_reply_instantiate_data(msg)
+        .map_err(|e| ContractError::Std(StdError::generic_err(e.to_string())))?;
+    let farm = deps.api.addr_validate(&res.contract_address)?;
+
+    // Register the new farm with the info stored before the instantiation
+    let pending_farm_info = PENDING_FARM_INFO.load(deps.storage)?;
+    PENDING_FARM_INFO.remove(deps.storage);
+
+    let farm_info = FarmInfo {
+        farm: farm.clone(),
+        staked_token: pending_farm_info.staked_token,
+        reward_tokens: pending_farm_info.reward_tokens,
+        farm_owner: Some(pending_farm_info.farm_owner.clone()),
+    };
+    farms().save(deps.storage, farm.clone(), &farm_info)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "register_farm"),
+        ("farm", farm.as_ref()),
+        ("staked_token", &farm_info.staked_token.to_string()),
+        ("reward_tokens", &tokens_to_string(&farm_info.reward_tokens)),
+        ("farm_owner", pending_farm_info.farm_owner.as_ref()),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => Ok(to_binary(&query_config(deps)?)?),
+        QueryMsg::Farm { address } => Ok(to_binary(&query_farm(deps, address)?)?),
+        QueryMsg::Farms { start_after, limit } => {
+            Ok(to_binary(&query_farms(deps, start_after, limit)?)?)
+        }
+        QueryMsg::FarmsByStakedToken {
+            staked_token,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_farms_by_staked_token(
+            deps,
+            staked_token,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::FarmsByOwner {
+            owner,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_farms_by_owner(
+            deps,
+            owner,
+            start_after,
+            limit,
+        )?)?),
+    }
+}
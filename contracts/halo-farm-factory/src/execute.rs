@@ -0,0 +1,126 @@
+use cosmwasm_std::{to_binary, DepsMut, Env, MessageInfo, Response, SubMsg, WasmMsg};
+use halo_farm::{
+    msg::{InstantiateMsg as FarmInstantiateMsg, QueryMsg as FarmQueryMsg},
+    state::{OwnershipResponse, TokenInfo},
+};
+
+use crate::{
+    contract::INSTANTIATE_FARM_REPLY_ID,
+    error::ContractError,
+    state::{farms, PendingFarmInfo, CONFIG, PENDING_FARM_INFO},
+};
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    farm_code_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Check if the message sender is the owner of the factory
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+
+    if let Some(farm_code_id) = farm_code_id {
+        config.farm_code_id = farm_code_id;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "update_config"),
+        ("owner", config.owner.as_ref()),
+        ("farm_code_id", &config.farm_code_id.to_string()),
+    ]))
+}
+
+pub fn execute_create_farm(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    create_farm_msg: FarmInstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Check if the message sender is the owner of the factory
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Keep the farm info until the reply gives us the new farm address
+    let pending_farm_info = PendingFarmInfo {
+        staked_token: create_farm_msg.staked_token.clone(),
+        reward_tokens: create_farm_msg
+            .reward_tokens
+            .iter()
+            .map(|reward_token| reward_token.info.clone())
+            .collect(),
+        farm_owner: create_farm_msg.farm_owner.clone(),
+    };
+    PENDING_FARM_INFO.save(deps.storage, &pending_farm_info)?;
+
+    // Instantiate the new farm, the factory owner is the admin of the farm contract
+    let instantiate_farm = SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            admin: Some(config.owner.to_string()),
+            code_id: config.farm_code_id,
+            msg: to_binary(&create_farm_msg)?,
+            funds: vec![],
+            label: format!("halo-farm {}", env.block.height),
+        },
+        INSTANTIATE_FARM_REPLY_ID,
+    );
+
+    Ok(Response::new()
+        .add_submessage(instantiate_farm)
+        .add_attributes([
+            ("method", "create_farm"),
+            ("staked_token", &create_farm_msg.staked_token.to_string()),
+            (
+                "reward_tokens",
+                &tokens_to_string(&pending_farm_info.reward_tokens),
+            ),
+            ("farm_owner", create_farm_msg.farm_owner.as_ref()),
+        ]))
+}
+
+pub fn execute_sync_farm_owner(deps: DepsMut, farm: String) -> Result<Response, ContractError> {
+    let farm = deps.api.addr_validate(&farm)?;
+    // Only the registered farms can be synced
+    let mut farm_info = farms().load(deps.storage, farm.clone())?;
+
+    // The farm owner is changed by the farm itself, so it is read from the farm
+    let ownership: OwnershipResponse = deps
+        .querier
+        .query_wasm_smart(farm.clone(), &FarmQueryMsg::Ownership {})?;
+    farm_info.farm_owner = ownership.owner;
+
+    // Save farm info, the farm owner index is updated too
+    farms().save(deps.storage, farm.clone(), &farm_info)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "sync_farm_owner"),
+        ("farm", farm.as_ref()),
+        (
+            "farm_owner",
+            &farm_info
+                .farm_owner
+                .map_or("none".to_string(), |farm_owner| farm_owner.to_string()),
+        ),
+    ]))
+}
+
+/// Join token infos into a comma separated string for the attributes.
+pub fn tokens_to_string(tokens: &[TokenInfo]) -> String {
+    tokens
+        .iter()
+        .map(|token| token.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
@@ -0,0 +1,58 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use halo_farm::msg::InstantiateMsg as FarmInstantiateMsg;
+
+use crate::state::{Config, FarmInfo};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Code id of the halo-farm contract used to instantiate new farms
+    pub farm_code_id: u64,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Update factory config by factory owner
+    UpdateConfig {
+        /// New factory owner
+        owner: Option<String>,
+        /// New halo-farm code id
+        farm_code_id: Option<u64>,
+    },
+    /// Instantiate a new farm contract and register it in the factory
+    CreateFarm {
+        /// Instantiate message of the new farm
+        create_farm_msg: FarmInstantiateMsg,
+    },
+    /// Update the registered farm owner from the `Ownership` query of the farm,
+    /// anyone can call it after the ownership of the farm is transferred or renounced
+    SyncFarmOwner {
+        /// Address of the registered farm
+        farm: String,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+    #[returns(FarmInfo)]
+    Farm { address: String },
+    #[returns(Vec<FarmInfo>)]
+    Farms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<FarmInfo>)]
+    FarmsByStakedToken {
+        staked_token: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<FarmInfo>)]
+    FarmsByOwner {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
@@ -0,0 +1,70 @@
+use cosmwasm_std::{Addr, Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::state::{farms, Config, FarmInfo, CONFIG};
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn query_config(deps: Deps) -> StdResult<Config> {
+    CONFIG.load(deps.storage)
+}
+
+pub fn query_farm(deps: Deps, address: String) -> StdResult<FarmInfo> {
+    let farm = deps.api.addr_validate(&address)?;
+    farms().load(deps.storage, farm)
+}
+
+pub fn query_farms(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FarmInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|farm| Bound::exclusive(Addr::unchecked(farm)));
+
+    farms()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, farm_info)| farm_info))
+        .collect()
+}
+
+pub fn query_farms_by_staked_token(
+    deps: Deps,
+    staked_token: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FarmInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|farm| Bound::exclusive(Addr::unchecked(farm)));
+
+    farms()
+        .idx
+        .staked_token
+        .prefix(staked_token)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, farm_info)| farm_info))
+        .collect()
+}
+
+pub fn query_farms_by_owner(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FarmInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|farm| Bound::exclusive(Addr::unchecked(farm)));
+
+    farms()
+        .idx
+        .farm_owner
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, farm_info)| farm_info))
+        .collect()
+}
@@ -0,0 +1,91 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex, Prefix};
+use halo_farm::state::TokenInfo;
+
+#[cw_serde]
+pub struct Config {
+    pub owner: Addr,
+    pub farm_code_id: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Registered info of a farm instantiated by the factory.
+#[cw_serde]
+pub struct FarmInfo {
+    pub farm: Addr,
+    pub staked_token: TokenInfo,
+    pub reward_tokens: Vec<TokenInfo>,
+    pub farm_owner: Option<Addr>, // None after the farm owner renounced the ownership
+}
+
+/// Farm info waiting for the instantiate reply to know its contract address.
+#[cw_serde]
+pub struct PendingFarmInfo {
+    pub staked_token: TokenInfo,
+    pub reward_tokens: Vec<TokenInfo>,
+    pub farm_owner: Addr,
+}
+
+pub const PENDING_FARM_INFO: Item<PendingFarmInfo> = Item::new("pending_farm_info");
+
+pub struct FarmIndexes<'a> {
+    pub staked_token: MultiIndex<'a, String, FarmInfo, Addr>,
+    pub farm_owner: FarmOwnerIndex<'a>,
+}
+
+/// Index of the farms by farm owner, the farms without owner are not indexed.
+pub struct FarmOwnerIndex<'a>(MultiIndex<'a, String, FarmInfo, Addr>);
+
+impl<'a> FarmOwnerIndex<'a> {
+    pub fn prefix(&self, farm_owner: String) -> Prefix<Addr, FarmInfo, Addr> {
+        self.0.prefix(farm_owner)
+    }
+}
+
+impl<'a> Index<FarmInfo> for FarmOwnerIndex<'a> {
+    fn save(&self, store: &mut dyn Storage, pk: &[u8], data: &FarmInfo) -> StdResult<()> {
+        if data.farm_owner.is_none() {
+            return Ok(());
+        }
+        self.0.save(store, pk, data)
+    }
+
+    fn remove(&self, store: &mut dyn Storage, pk: &[u8], old_data: &FarmInfo) -> StdResult<()> {
+        if old_data.farm_owner.is_none() {
+            return Ok(());
+        }
+        self.0.remove(store, pk, old_data)
+    }
+}
+
+impl<'a> IndexList<FarmInfo> for FarmIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<FarmInfo>> + '_> {
+        let v: Vec<&dyn Index<FarmInfo>> = vec![&self.staked_token, &self.farm_owner];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Mapping from farm contract address to farm info, indexed by staked token (contract address
+/// or denom) and farm owner.
+pub fn farms<'a>() -> IndexedMap<'a, Addr, FarmInfo, FarmIndexes<'a>> {
+    let indexes = FarmIndexes {
+        staked_token: MultiIndex::new(
+            |_pk, farm| farm.staked_token.to_string(),
+            "farms",
+            "farms__staked_token",
+        ),
+        // The index function is only called for the farms with owner
+        farm_owner: FarmOwnerIndex(MultiIndex::new(
+            |_pk, farm| {
+                farm.farm_owner
+                    .as_ref()
+                    .map_or_else(String::new, |farm_owner| farm_owner.to_string())
+            },
+            "farms",
+            "farms__farm_owner",
+        )),
+    };
+    IndexedMap::new("farms", indexes)
+}
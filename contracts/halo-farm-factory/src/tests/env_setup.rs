@@ -0,0 +1,80 @@
+#[cfg(test)]
+pub mod env {
+    use cosmwasm_std::{Addr, Empty};
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+    use halo_farm::contract::{
+        execute as HaloFarmExecute, instantiate as HaloFarmInstantiate, query as HaloFarmQuery,
+    };
+
+    use crate::contract::{
+        execute as HaloFarmFactoryExecute, instantiate as HaloFarmFactoryInstantiate,
+        query as HaloFarmFactoryQuery, reply as HaloFarmFactoryReply,
+    };
+    use crate::msg::InstantiateMsg as HaloFarmFactoryInstantiateMsg;
+
+    pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
+    pub const USER_1: &str = "aura1000000000000000000000000000000000user1";
+
+    pub struct ContractInfo {
+        pub contract_addr: String,
+        pub farm_code_id: u64,
+    }
+
+    fn mock_app() -> App {
+        AppBuilder::new().build(|_, _, _| {})
+    }
+
+    fn halo_farm_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(HaloFarmExecute, HaloFarmInstantiate, HaloFarmQuery);
+        Box::new(contract)
+    }
+
+    fn halo_farm_factory_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            HaloFarmFactoryExecute,
+            HaloFarmFactoryInstantiate,
+            HaloFarmFactoryQuery,
+        )
+        .with_reply(HaloFarmFactoryReply);
+        Box::new(contract)
+    }
+
+    pub fn instantiate_contracts() -> (App, Vec<ContractInfo>) {
+        // Create a new app instance
+        let mut app = mock_app();
+        // Create a vector to store all contract info ([halo farm factory - [0])
+        let mut contract_info_vec: Vec<ContractInfo> = Vec::new();
+
+        // store code of all contracts to the app and get the code ids
+        let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+        let halo_farm_factory_contract_code_id =
+            app.store_code(halo_farm_factory_contract_template());
+
+        // halo farm factory contract
+        // create instantiate message for contract
+        let halo_farm_factory_instantiate_msg = HaloFarmFactoryInstantiateMsg {
+            farm_code_id: halo_farm_contract_code_id,
+        };
+
+        // instantiate contract
+        let halo_farm_factory_contract_addr = app
+            .instantiate_contract(
+                halo_farm_factory_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &halo_farm_factory_instantiate_msg,
+                &[],
+                "test instantiate contract",
+                None,
+            )
+            .unwrap();
+
+        // add contract info to the vector
+        contract_info_vec.push(ContractInfo {
+            contract_addr: halo_farm_factory_contract_addr.to_string(),
+            farm_code_id: halo_farm_contract_code_id,
+        });
+
+        (app, contract_info_vec)
+    }
+}
@@ -0,0 +1,488 @@
+#![cfg(test)]
+mod tests {
+    const LP_TOKEN_1: &str = "aura1000000000000000000000000000000lptoken1";
+    const LP_TOKEN_2: &str = "aura1000000000000000000000000000000lptoken2";
+    const REWARD_DENOM: &str = "uaura";
+
+    mod execute_proper_operation {
+        use cosmwasm_std::{Addr, BlockInfo};
+        use cw_multi_test::{App, Executor};
+        use halo_farm::{
+            msg::{
+                ExecuteMsg as FarmExecuteMsg, InstantiateMsg as FarmInstantiateMsg,
+                PhaseRewardToken, QueryMsg as FarmQueryMsg,
+            },
+            state::{FarmInfoResponse as HaloFarmInfo, TokenInfo},
+        };
+
+        use crate::{
+            msg::{ExecuteMsg as FactoryExecuteMsg, QueryMsg as FactoryQueryMsg},
+            state::{Config, FarmInfo},
+            tests::{
+                env_setup::env::{instantiate_contracts, ADMIN, USER_1},
+                integration_test::tests::{LP_TOKEN_1, LP_TOKEN_2, REWARD_DENOM},
+            },
+        };
+
+        // create farm instantiate message starting from the current block time
+        fn create_farm_msg(app: &App, staked_token: &str, farm_owner: &str) -> FarmInstantiateMsg {
+            let current_block_time = app.block_info().time.seconds();
+
+            FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(staked_token),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: REWARD_DENOM.to_string(),
+                    },
+                    whitelist: Addr::unchecked(farm_owner),
+                }],
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 110,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(farm_owner),
+            }
+        }
+
+        // Create 3 farms by factory owner:
+        // - Farm 1: LP_TOKEN_1 owned by ADMIN
+        // - Farm 2: LP_TOKEN_2 owned by USER_1
+        // - Farm 3: LP_TOKEN_1 owned by USER_1
+        // Query farms by staked token and by owner with pagination
+        #[test]
+        fn proper_create_farms() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get factory contract
+            let factory_contract = Addr::unchecked(contracts[0].contract_addr.clone());
+
+            // query factory config
+            let config: Config = app
+                .wrap()
+                .query_wasm_smart(factory_contract.clone(), &FactoryQueryMsg::Config {})
+                .unwrap();
+
+            assert_eq!(
+                config,
+                Config {
+                    owner: Addr::unchecked(ADMIN),
+                    farm_code_id: contracts[0].farm_code_id,
+                }
+            );
+
+            let mut created_farms: Vec<FarmInfo> = vec![];
+            for (staked_token, farm_owner) in [
+                (LP_TOKEN_1, ADMIN),
+                (LP_TOKEN_2, USER_1),
+                (LP_TOKEN_1, USER_1),
+            ] {
+                let create_farm_msg = FactoryExecuteMsg::CreateFarm {
+                    create_farm_msg: create_farm_msg(&app, staked_token, farm_owner),
+                };
+
+                // Execute create farm
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN),
+                    factory_contract.clone(),
+                    &create_farm_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+
+                // get the new farm address from the instantiate event
+                let farm = response
+                    .unwrap()
+                    .events
+                    .iter()
+                    .find(|event| event.ty == "instantiate")
+                    .unwrap()
+                    .attributes
+                    .iter()
+                    .find(|attr| attr.key == "_contract_addr")
+                    .unwrap()
+                    .value
+                    .clone();
+
+                // query registered farm info
+                let farm_info: FarmInfo = app
+                    .wrap()
+                    .query_wasm_smart(
+                        factory_contract.clone(),
+                        &FactoryQueryMsg::Farm {
+                            address: farm.clone(),
+                        },
+                    )
+                    .unwrap();
+
+                assert_eq!(
+                    farm_info,
+                    FarmInfo {
+                        farm: Addr::unchecked(farm.clone()),
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(staked_token),
+                        },
+                        reward_tokens: vec![TokenInfo::NativeToken {
+                            denom: REWARD_DENOM.to_string(),
+                        }],
+                        farm_owner: Some(Addr::unchecked(farm_owner)),
+                    }
+                );
+
+                // query the farm contract itself
+                let halo_farm_info: HaloFarmInfo = app
+                    .wrap()
+                    .query_wasm_smart(farm.clone(), &FarmQueryMsg::Farm {})
+                    .unwrap();
+
+                assert_eq!(
+                    halo_farm_info.staked_token,
+                    TokenInfo::Token {
+                        contract_addr: Addr::unchecked(staked_token),
+                    }
+                );
+
+                // the farm is instantiated from the stored farm code id
+                let contract_info = app.wrap().query_wasm_contract_info(farm).unwrap();
+                assert_eq!(contract_info.code_id, contracts[0].farm_code_id);
+                assert_eq!(contract_info.admin, Some(ADMIN.to_string()));
+
+                created_farms.push(farm_info);
+
+                // increase 1 second for the next farm
+                app.set_block(BlockInfo {
+                    time: app.block_info().time.plus_seconds(1),
+                    height: app.block_info().height + 1,
+                    chain_id: app.block_info().chain_id,
+                });
+            }
+
+            // query all farms
+            let farms: Vec<FarmInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &FactoryQueryMsg::Farms {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(farms.len(), 3);
+
+            // query farms by LP_TOKEN_1
+            let farms: Vec<FarmInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &FactoryQueryMsg::FarmsByStakedToken {
+                        staked_token: LP_TOKEN_1.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            let mut expected_farms = vec![created_farms[0].clone(), created_farms[2].clone()];
+            expected_farms.sort_by(|a, b| a.farm.cmp(&b.farm));
+            assert_eq!(farms, expected_farms);
+
+            // query farms by LP_TOKEN_1 with limit 1
+            let farms: Vec<FarmInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &FactoryQueryMsg::FarmsByStakedToken {
+                        staked_token: LP_TOKEN_1.to_string(),
+                        start_after: None,
+                        limit: Some(1),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(farms, vec![expected_farms[0].clone()]);
+
+            // query next page of farms by LP_TOKEN_1
+            let farms: Vec<FarmInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &FactoryQueryMsg::FarmsByStakedToken {
+                        staked_token: LP_TOKEN_1.to_string(),
+                        start_after: Some(farms[0].farm.to_string()),
+                        limit: Some(1),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(farms, vec![expected_farms[1].clone()]);
+
+            // query farms owned by USER_1
+            let farms: Vec<FarmInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &FactoryQueryMsg::FarmsByOwner {
+                        owner: USER_1.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            let mut expected_farms = vec![created_farms[1].clone(), created_farms[2].clone()];
+            expected_farms.sort_by(|a, b| a.farm.cmp(&b.farm));
+            assert_eq!(farms, expected_farms);
+
+            // query farms owned by ADMIN
+            let farms: Vec<FarmInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract,
+                    &FactoryQueryMsg::FarmsByOwner {
+                        owner: ADMIN.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(farms, vec![created_farms[0].clone()]);
+        }
+
+        // Only factory owner can create farm and update config
+        #[test]
+        fn create_farm_and_update_config_by_non_owner() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get factory contract
+            let factory_contract = Addr::unchecked(contracts[0].contract_addr.clone());
+
+            let create_farm_msg = FactoryExecuteMsg::CreateFarm {
+                create_farm_msg: create_farm_msg(&app, LP_TOKEN_1, USER_1),
+            };
+
+            // Execute create farm by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                factory_contract.clone(),
+                &create_farm_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized".to_string()
+            );
+
+            let update_config_msg = FactoryExecuteMsg::UpdateConfig {
+                owner: Some(USER_1.to_string()),
+                farm_code_id: None,
+            };
+
+            // Execute update config by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                factory_contract.clone(),
+                &update_config_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().source().unwrap().to_string(),
+                "Unauthorized".to_string()
+            );
+
+            // Execute update config by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                factory_contract.clone(),
+                &update_config_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 is the new factory owner and can create farm
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                factory_contract.clone(),
+                &create_farm_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // query farms owned by USER_1
+            let farms: Vec<FarmInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract,
+                    &FactoryQueryMsg::FarmsByOwner {
+                        owner: USER_1.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(farms.len(), 1);
+        }
+
+        // query farms owned by the given owner
+        fn query_farms_by_owner(app: &App, factory: &Addr, owner: &str) -> Vec<FarmInfo> {
+            app.wrap()
+                .query_wasm_smart(
+                    factory.clone(),
+                    &FactoryQueryMsg::FarmsByOwner {
+                        owner: owner.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        }
+
+        // Create a farm owned by ADMIN
+        // Transfer the ownership of the farm to USER_1 -> the factory still lists ADMIN as owner
+        // Sync the farm owner by USER_1 -> the farm is listed for USER_1 and not for ADMIN
+        // Renounce the ownership by USER_1 and sync -> the farm has no owner and is not listed
+        // Sync a farm which is not registered -> error
+        #[test]
+        fn proper_sync_farm_owner() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get factory contract
+            let factory_contract = Addr::unchecked(contracts[0].contract_addr.clone());
+
+            // Execute create farm owned by ADMIN
+            let response = app
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    factory_contract.clone(),
+                    &FactoryExecuteMsg::CreateFarm {
+                        create_farm_msg: create_farm_msg(&app, LP_TOKEN_1, ADMIN),
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            // get the new farm address from the instantiate event
+            let farm = Addr::unchecked(
+                response
+                    .events
+                    .iter()
+                    .find(|event| event.ty == "instantiate")
+                    .unwrap()
+                    .attributes
+                    .iter()
+                    .find(|attr| attr.key == "_contract_addr")
+                    .unwrap()
+                    .value
+                    .clone(),
+            );
+
+            // Transfer the ownership of the farm from ADMIN to USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                farm.clone(),
+                &FarmExecuteMsg::ProposeNewOwner {
+                    owner: USER_1.to_string(),
+                    expiry: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                farm.clone(),
+                &FarmExecuteMsg::AcceptOwnership {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // The factory is not synced yet
+            assert_eq!(
+                query_farms_by_owner(&app, &factory_contract, ADMIN).len(),
+                1
+            );
+            assert!(query_farms_by_owner(&app, &factory_contract, USER_1).is_empty());
+
+            // Execute sync farm owner by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                factory_contract.clone(),
+                &FactoryExecuteMsg::SyncFarmOwner {
+                    farm: farm.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // The farm is listed for USER_1 only
+            let farms = query_farms_by_owner(&app, &factory_contract, USER_1);
+            assert_eq!(farms.len(), 1);
+            assert_eq!(farms[0].farm, farm);
+            assert_eq!(farms[0].farm_owner, Some(Addr::unchecked(USER_1)));
+            assert!(query_farms_by_owner(&app, &factory_contract, ADMIN).is_empty());
+
+            // Renounce the ownership of the farm by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                farm.clone(),
+                &FarmExecuteMsg::RenounceOwnership {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute sync farm owner by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                factory_contract.clone(),
+                &FactoryExecuteMsg::SyncFarmOwner {
+                    farm: farm.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // The farm has no owner anymore
+            let farm_info: FarmInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &FactoryQueryMsg::Farm {
+                        address: farm.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(farm_info.farm_owner, None);
+            assert!(query_farms_by_owner(&app, &factory_contract, USER_1).is_empty());
+            // The farm without owner is not indexed by an empty owner
+            assert!(query_farms_by_owner(&app, &factory_contract, "").is_empty());
+
+            // Execute sync farm owner of a farm which is not registered
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                factory_contract,
+                &FactoryExecuteMsg::SyncFarmOwner {
+                    farm: LP_TOKEN_2.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+        }
+    }
+}
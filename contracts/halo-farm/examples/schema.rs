@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 
-use halo_farm::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use halo_farm::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
-        query: QueryMsg
+        query: QueryMsg,
+        migrate: MigrateMsg
     }
 }
@@ -3,10 +3,10 @@ use std::env;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
-    Uint128,
+    to_binary, Binary, Decimal256, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:halo-farm";
@@ -15,14 +15,27 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 use crate::{
     error::ContractError,
     execute::{
-        execute_activate_phase, execute_add_phase, execute_add_reward_balance, execute_deposit,
-        execute_harvest, execute_remove_phase, execute_withdraw, validate_time_range,
+        execute_accept_ownership, execute_activate_phase, execute_add_phase,
+        execute_add_reward_balance, execute_approve_operator, execute_cancel_ownership_proposal,
+        execute_compound, execute_deposit_native, execute_emergency_withdraw, execute_extend_phase,
+        execute_harvest, execute_harvest_for, execute_propose_new_owner, execute_receive,
+        execute_reclaim_undistributed, execute_remove_phase, execute_remove_reward_balance,
+        execute_renounce_ownership, execute_revoke_operator, execute_set_paused,
+        execute_settle_phases, execute_top_up_active_phase, execute_update_lock_tiers,
+        execute_update_phases_limit_per_user, execute_withdraw, validate_lock_tiers,
+        validate_phases_limit_per_user, validate_reward_tokens, validate_time_range,
     },
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    migrations::migrate_state,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     query::{
-        query_farm_info, query_pending_reward, query_staker_info, query_total_lp_token_staked,
+        query_all_stakers, query_config, query_current_phase, query_farm_info, query_locks,
+        query_operators, query_ownership, query_pending_reward, query_phase,
+        query_phase_settlement, query_phases, query_reward_rate, query_staker_info,
+        query_staking_capacity, query_total_lp_token_staked,
+    },
+    state::{
+        Config, FarmInfo, PhaseInfo, RewardTokenInfo, TokenInfo, CONFIG, FARM_INFO, PHASES_INFO,
     },
-    state::{Config, FarmInfo, PhaseInfo, TokenInfo, CONFIG, FARM_INFO},
 };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -37,56 +50,63 @@ pub fn instantiate(
     validate_time_range(env, msg.start_time, msg.end_time)?;
 
     // Validate staked token format
-    if deps.api.addr_validate(msg.staked_token.as_ref()).is_err() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Invalid staked token address",
-        )));
-    }
-
-    // Validate reward token format
-    match msg.reward_token {
+    match msg.staked_token {
         TokenInfo::NativeToken { ref denom } => {
             if denom.is_empty() {
-                return Err(ContractError::Std(StdError::generic_err(
-                    "Reward denom is empty",
-                )));
+                return Err(ContractError::EmptyStakedDenom {});
             }
         }
         TokenInfo::Token { ref contract_addr } => {
             if deps.api.addr_validate(contract_addr.as_ref()).is_err() {
-                return Err(ContractError::Std(StdError::generic_err(
-                    "Invalid reward token address",
-                )));
+                return Err(ContractError::InvalidTokenAddress {
+                    address: contract_addr.to_string(),
+                });
             }
         }
     }
 
-    // Validate whitelist format
-    if deps.api.addr_validate(msg.whitelist.as_ref()).is_err() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Invalid whitelist address",
-        )));
-    }
+    // Validate reward tokens and their whitelists
+    validate_reward_tokens(deps.api, &msg.reward_tokens)?;
 
     // Validate phases limit per user
-    if msg.phases_limit_per_user.is_some() && msg.phases_limit_per_user.unwrap().is_zero() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Invalid phases limit per user",
-        )));
+    validate_phases_limit_per_user(msg.phases_limit_per_user)?;
+
+    // Validate lock tiers
+    validate_lock_tiers(&msg.lock_tiers)?;
+
+    // Validate max total staked
+    if msg.max_total_staked.map_or(false, |limit| limit.is_zero()) {
+        return Err(ContractError::InvalidMaxTotalStaked {});
     }
 
     let config = Config {
-        farm_owner: msg.farm_owner,
+        farm_owner: Some(msg.farm_owner),
+        paused: false,
+        max_total_staked: msg.max_total_staked,
+        lock_tiers: msg.lock_tiers,
     };
 
     // Init phase info
     let phase_info = PhaseInfo {
         start_time: msg.start_time,
         end_time: msg.end_time,
-        whitelist: msg.whitelist,
-        reward_balance: Uint128::zero(),
+        reward_tokens: msg
+            .reward_tokens
+            .iter()
+            .map(|reward_token| RewardTokenInfo {
+                info: reward_token.info.clone(),
+                whitelist: reward_token.whitelist.clone(),
+                reward_balance: Uint128::zero(),
+                accrued_token_per_share: Decimal256::zero(),
+                undistributed_reward: Uint128::zero(),
+                distributed_reward: Uint128::zero(),
+                claimed_reward: Uint128::zero(),
+                removed_reward: Uint128::zero(),
+                emitted_reward: Uint128::zero(),
+            })
+            .collect(),
         last_reward_time: msg.start_time,
-        accrued_token_per_share: Decimal::zero(),
+        emission_start_time: None,
     };
 
     // Init first phase info
@@ -94,21 +114,31 @@ pub fn instantiate(
         deps.storage,
         &FarmInfo {
             staked_token: msg.staked_token.clone(),
-            reward_token: msg.reward_token.clone(),
             current_phase_index: 0u64,
-            phases_info: vec![phase_info],
+            phases_count: 1u64,
             phases_limit_per_user: msg.phases_limit_per_user,
             staked_token_balance: Uint128::zero(),
+            boost_weight: Uint128::zero(),
         },
     )?;
 
+    // Save the first phase
+    PHASES_INFO.save(deps.storage, 0u64, &phase_info)?;
+
     // Save config
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes([
         ("method", "instantiate"),
-        ("staked_token", msg.staked_token.as_ref()),
-        ("reward_token", &msg.reward_token.to_string()),
+        ("staked_token", &msg.staked_token.to_string()),
+        (
+            "reward_tokens",
+            &msg.reward_tokens
+                .iter()
+                .map(|reward_token| reward_token.info.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
         ("start_time", &msg.start_time.to_string()),
         ("end_time", &msg.end_time.to_string()),
         (
@@ -128,31 +158,155 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
         ExecuteMsg::AddRewardBalance {
             phase_index,
             amount,
         } => execute_add_reward_balance(deps, env, info, phase_index, amount),
-        ExecuteMsg::Deposit { amount } => execute_deposit(deps, env, info, amount),
-        ExecuteMsg::Withdraw { amount } => execute_withdraw(deps, env, info, amount),
-        ExecuteMsg::Harvest {} => execute_harvest(deps, env, info),
+        ExecuteMsg::Deposit { lock_duration } => {
+            execute_deposit_native(deps, env, info, lock_duration)
+        }
+        ExecuteMsg::Withdraw { amount, recipient } => {
+            execute_withdraw(deps, env, info, amount, recipient)
+        }
+        ExecuteMsg::Harvest { recipient } => execute_harvest(deps, env, info, recipient),
+        ExecuteMsg::HarvestFor { staker } => execute_harvest_for(deps, env, info, staker),
+        ExecuteMsg::ApproveOperator {
+            operator,
+            recipient,
+        } => execute_approve_operator(deps, info, operator, recipient),
+        ExecuteMsg::RevokeOperator { operator } => execute_revoke_operator(deps, info, operator),
+        ExecuteMsg::SettlePhases { up_to } => execute_settle_phases(deps, env, info, up_to),
+        ExecuteMsg::Compound {} => execute_compound(deps, env, info),
+        ExecuteMsg::EmergencyWithdraw {} => execute_emergency_withdraw(deps, env, info),
         ExecuteMsg::AddPhase {
             new_start_time,
             new_end_time,
-            whitelist,
-        } => execute_add_phase(deps, env, info, new_start_time, new_end_time, whitelist),
+            reward_tokens,
+        } => execute_add_phase(deps, env, info, new_start_time, new_end_time, reward_tokens),
         ExecuteMsg::ActivatePhase {} => execute_activate_phase(deps, env, info),
-        ExecuteMsg::RemovePhase { phase_index } => execute_remove_phase(deps, info, phase_index),
+        ExecuteMsg::ExtendPhase { new_end_time } => {
+            execute_extend_phase(deps, env, info, new_end_time)
+        }
+        ExecuteMsg::TopUpActivePhase { amount } => {
+            execute_top_up_active_phase(deps, env, info, amount)
+        }
+        ExecuteMsg::ReclaimUndistributed { phase_index } => {
+            execute_reclaim_undistributed(deps, env, info, phase_index)
+        }
+        ExecuteMsg::RemoveRewardBalance { phase_index } => {
+            execute_remove_reward_balance(deps, env, info, phase_index)
+        }
+        ExecuteMsg::RemovePhase { phase_index } => {
+            execute_remove_phase(deps, env, info, phase_index)
+        }
+        ExecuteMsg::ProposeNewOwner { owner, expiry } => {
+            execute_propose_new_owner(deps, env, info, owner, expiry)
+        }
+        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
+        ExecuteMsg::CancelOwnershipProposal {} => execute_cancel_ownership_proposal(deps, info),
+        ExecuteMsg::RenounceOwnership {} => execute_renounce_ownership(deps, info),
+        ExecuteMsg::UpdatePhasesLimitPerUser {
+            new_phases_limit_per_user,
+        } => execute_update_phases_limit_per_user(deps, info, new_phases_limit_per_user),
+        ExecuteMsg::UpdateLockTiers { new_lock_tiers } => {
+            execute_update_lock_tiers(deps, info, new_lock_tiers)
+        }
+        ExecuteMsg::Pause {} => execute_set_paused(deps, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, info, false),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Farm {} => Ok(to_binary(&query_farm_info(deps)?)?),
+        QueryMsg::Config {} => Ok(to_binary(&query_config(deps)?)?),
+        QueryMsg::Farm {} => Ok(to_binary(&query_farm_info(deps, env)?)?),
         QueryMsg::PendingReward { address } => {
             Ok(to_binary(&query_pending_reward(deps, env, address)?)?)
         }
         QueryMsg::TotalStaked {} => Ok(to_binary(&query_total_lp_token_staked(deps)?)?),
         QueryMsg::StakerInfo { address } => Ok(to_binary(&query_staker_info(deps, address)?)?),
+        QueryMsg::Ownership {} => Ok(to_binary(&query_ownership(deps)?)?),
+        QueryMsg::AllStakers { start_after, limit } => Ok(to_binary(&query_all_stakers(
+            deps,
+            env,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::StakingCapacity {} => Ok(to_binary(&query_staking_capacity(deps)?)?),
+        QueryMsg::Locks { address } => Ok(to_binary(&query_locks(deps, env, address)?)?),
+        QueryMsg::Operators {
+            staker,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_operators(
+            deps,
+            staker,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::PhaseSettlement { phase_index } => {
+            Ok(to_binary(&query_phase_settlement(deps, env, phase_index)?)?)
+        }
+        QueryMsg::Phase { phase_index } => Ok(to_binary(&query_phase(deps, env, phase_index)?)?),
+        QueryMsg::Phases { start_after, limit } => {
+            Ok(to_binary(&query_phases(deps, env, start_after, limit)?)?)
+        }
+        QueryMsg::CurrentPhase {} => Ok(to_binary(&query_current_phase(deps, env)?)?),
+        QueryMsg::RewardRate {
+            deposit_amount,
+            duration,
+            lock_duration,
+        } => Ok(to_binary(&query_reward_rate(
+            deps,
+            env,
+            deposit_amount,
+            duration,
+            lock_duration,
+        )?)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+
+    // Only allow migrating from the halo-farm contract
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName {
+            expected: CONTRACT_NAME.to_string(),
+            stored: stored.contract,
+        });
+    }
+
+    let stored_version = parse_version(&stored.version)?;
+    let new_version = parse_version(CONTRACT_VERSION)?;
+
+    // Not allow migrating to an older version
+    if stored_version > new_version {
+        return Err(ContractError::MigrationDowngrade {
+            stored: stored.version,
+            new: CONTRACT_VERSION.to_string(),
+        });
     }
+
+    // Upgrade the state written by the stored version
+    migrate_state(deps.storage, &stored_version)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "migrate"),
+        ("from_version", &stored.version),
+        ("to_version", CONTRACT_VERSION),
+    ]))
+}
+
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    version
+        .parse()
+        .map_err(|_| ContractError::InvalidContractVersion {
+            version: version.to_string(),
+        })
 }
@@ -1,8 +1,187 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Addr, StdError, Uint128};
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Farm is paused")]
+    Paused {},
+
+    #[error("Can not renounce the ownership while the farm is paused")]
+    RenounceWhilePaused {},
+
+    #[error("Ownership proposal not found")]
+    NoOwnershipProposal {},
+
+    #[error("Ownership proposal expired")]
+    OwnershipProposalExpired {},
+
+    #[error("Invalid contract name: expected {expected}, stored {stored}")]
+    InvalidContractName { expected: String, stored: String },
+
+    #[error("Invalid contract version: {version}")]
+    InvalidContractVersion { version: String },
+
+    #[error("Can not migrate from version {stored} to older version {new}")]
+    MigrationDowngrade { stored: String, new: String },
+
+    #[error("Unauthorized: {sender} is not whitelisted address")]
+    NotWhitelisted { sender: Addr },
+
+    #[error("Unauthorized: {address} is not staker")]
+    NotStaker { address: Addr },
+
+    #[error("Unauthorized: {operator} is not an approved operator of {staker}")]
+    OperatorNotApproved { operator: Addr, staker: Addr },
+
+    #[error("Unauthorized: {token} is not the staked token")]
+    InvalidStakedToken { token: Addr },
+
+    #[error("Invalid action: Use cw20 send to deposit cw20 staked token")]
+    Cw20StakedToken {},
+
+    #[error("Native token balance mismatch: expected {expected}, received {received}")]
+    FundsMismatch {
+        expected: Uint128,
+        received: Uint128,
+    },
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("Staked denom is empty")]
+    EmptyStakedDenom {},
+
+    #[error("Reward denom is empty")]
+    EmptyRewardDenom {},
+
+    #[error("Reward tokens are empty")]
+    EmptyRewardTokens {},
+
+    #[error("Invalid token address: {address}")]
+    InvalidTokenAddress { address: String },
+
+    #[error("Invalid whitelist address: {address}")]
+    InvalidWhitelist { address: String },
+
+    #[error("Duplicated reward token: {token}")]
+    DuplicatedRewardToken { token: String },
+
+    #[error("Invalid reward token: {token} is not a reward token of phase {phase_index}")]
+    InvalidRewardToken { token: String, phase_index: u64 },
+
+    #[error("Invalid phases limit per user")]
+    InvalidPhasesLimitPerUser {},
+
+    #[error("Invalid max total staked")]
+    InvalidMaxTotalStaked {},
+
+    #[error("Invalid lock tiers")]
+    InvalidLockTiers {},
+
+    #[error("Invalid lock duration: {duration} is not a lock tier")]
+    InvalidLockDuration { duration: u64 },
+
+    #[error("Stake is locked: unlocked {unlocked}, requested {requested}")]
+    LockedStake {
+        unlocked: Uint128,
+        requested: Uint128,
+    },
+
+    #[error("Invalid time range: start time {start_time} is not less than end time {end_time}")]
+    InvalidTimeRange { start_time: u64, end_time: u64 },
+
+    #[error("Start time {start_time} is less than current time {current_time}")]
+    StartTimeInPast { start_time: u64, current_time: u64 },
+
+    #[error("Phase {phase_index} not found")]
+    PhaseNotFound { phase_index: u64 },
+
+    #[error("Phase {phase_index} is not activated")]
+    PhaseNotActivated { phase_index: u64 },
+
+    #[error("Phase {phase_index} does not end until {end_time}")]
+    PhaseNotEnded { phase_index: u64, end_time: u64 },
+
+    #[error("No undistributed reward in phase {phase_index}")]
+    NoUndistributedReward { phase_index: u64 },
+
+    #[error("No surplus reward in phase {phase_index}")]
+    NoSurplusReward { phase_index: u64 },
+
+    #[error("Phase {phase_index} is empty")]
+    EmptyPhase { phase_index: u64 },
+
+    #[error("Phase {phase_index} is already activated")]
+    PhaseAlreadyActivated { phase_index: u64 },
+
+    #[error("Phase {phase_index} already started at {start_time}")]
+    PhaseStarted { phase_index: u64, start_time: u64 },
+
+    #[error("Phase {phase_index} already ended at {end_time}")]
+    PhaseEnded { phase_index: u64, end_time: u64 },
+
+    #[error("New end time {new_end_time} is not greater than end time {end_time}")]
+    InvalidNewEndTime { end_time: u64, new_end_time: u64 },
+
+    #[error(
+        "New start time {new_start_time} is less than end time {end_time} of the current phase"
+    )]
+    PhaseOverlap { new_start_time: u64, end_time: u64 },
+
+    #[error("Previous phase is not active")]
+    PreviousPhaseNotActive {},
+
+    #[error("Invalid action: Add new phase first")]
+    NoPhaseToActivate {},
+
+    #[error(
+        "Current time {current_time} is not in range [{earliest}, {latest}] to activate the phase"
+    )]
+    InvalidActivationTime {
+        current_time: u64,
+        earliest: u64,
+        latest: u64,
+    },
+
+    #[error("Deposit amount exceeds phase limit per user: limit {limit}, requested {requested}")]
+    ExceedsUserLimit { limit: Uint128, requested: Uint128 },
+
+    #[error("Deposit amount exceeds max total staked: limit {limit}, requested {requested}")]
+    ExceedsMaxTotalStaked { limit: Uint128, requested: Uint128 },
+
+    #[error("Insufficient stake: staked {staked}, requested {requested}")]
+    InsufficientStake { staked: Uint128, requested: Uint128 },
+
+    #[error("Can not compound: staked token {token} is not a reward token")]
+    CompoundNotSupported { token: String },
+
+    #[error(
+        "Invalid phase to settle up to: {up_to} is not in range ({joined_phase}, {current_phase_index}]"
+    )]
+    InvalidSettlePhases {
+        up_to: u64,
+        joined_phase: u64,
+        current_phase_index: u64,
+    },
+
+    #[error(
+        "Too many phases to settle from {joined_phase} to {current_phase_index}: settle the ended phases with SettlePhases first"
+    )]
+    TooManyPhasesToSettle {
+        joined_phase: u64,
+        current_phase_index: u64,
+    },
+
+    #[error("Reward amount is zero")]
+    NoReward {},
 }
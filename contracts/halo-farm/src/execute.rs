@@ -1,13 +1,68 @@
 use crate::{
     error::ContractError,
-    formulas::calc_reward_amount,
-    state::{Config, FarmInfo, PhaseInfo, StakerInfo, TokenInfo, CONFIG, FARM_INFO, STAKERS_INFO},
+    formulas::{calc_accrued_reward, calc_reward_amount},
+    msg::{Cw20HookMsg, PhaseRewardToken},
+    state::{
+        has_stakers, Config, FarmInfo, Lock, LockTier, OwnershipProposal, PhaseInfo, Phases,
+        RewardTokenAsset, RewardTokenInfo, StakerInfo, TokenInfo, CONFIG, FARM_INFO, OPERATORS,
+        OWNERSHIP_PROPOSAL, PHASES_INFO, STAKERS_INFO,
+    },
 };
 use cosmwasm_std::{
-    coins, has_coins, wasm_execute, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env,
-    MessageInfo, Response, StdError, Uint128,
+    coins, from_binary, wasm_execute, Addr, Api, BankMsg, CosmosMsg, Decimal, Decimal256, DepsMut,
+    Env, Event, MessageInfo, Response, StdResult, Storage, Uint128, Uint256,
 };
-use cw20::Cw20ExecuteMsg;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_utils::{must_pay, one_coin, Expiration};
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Deposit { lock_duration } => {
+            let farm_info = FARM_INFO.load(deps.storage)?;
+
+            // Only staked token contract can execute deposit hook
+            match farm_info.staked_token {
+                TokenInfo::Token { contract_addr } if contract_addr == info.sender => {}
+                _ => {
+                    return Err(ContractError::InvalidStakedToken { token: info.sender });
+                }
+            }
+
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            execute_deposit(deps, env, sender, cw20_msg.amount, lock_duration)
+        }
+        Cw20HookMsg::AddRewardBalance { phase_index } => {
+            // The sender contract is the cw20 reward token to be added
+            let reward_token = TokenInfo::Token {
+                contract_addr: info.sender,
+            };
+
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            add_reward_balance(
+                deps,
+                env,
+                sender,
+                phase_index,
+                reward_token,
+                cw20_msg.amount,
+            )
+        }
+        Cw20HookMsg::TopUpActivePhase {} => {
+            // The sender contract is the cw20 reward token to be added
+            let reward_token = TokenInfo::Token {
+                contract_addr: info.sender,
+            };
+
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            top_up_active_phase(deps, env, sender, reward_token, cw20_msg.amount)
+        }
+    }
+}
 
 pub fn execute_add_reward_balance(
     deps: DepsMut,
@@ -16,56 +71,76 @@ pub fn execute_add_reward_balance(
     phase_index: u64,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    // Verify reward token asset
+    // Match reward token type:
+    // 1. If reward token is native token, sender must add balance amount of native token
+    //    to the new farm contract address by sending via funds when calling this msg.
+    // 2. If reward token is cw20 token, sender must add balance amount of cw20 token
+    //    by calling cw20 contract send method with AddRewardBalance hook message.
+    let fund = one_coin(&info)?;
+
+    // Check the amount of native token sent is equal to the argument
+    if fund.amount != amount {
+        return Err(ContractError::FundsMismatch {
+            expected: amount,
+            received: fund.amount,
+        });
+    }
+
+    let reward_token = TokenInfo::NativeToken { denom: fund.denom };
+
+    add_reward_balance(deps, env, info.sender, phase_index, reward_token, amount)
+}
+
+// Add reward balance that already arrived at the farm contract to a phase
+fn add_reward_balance(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    phase_index: u64,
+    reward_token: TokenInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // Not allow adding reward balance when the farm is paused
+    if CONFIG.load(deps.storage)?.paused {
+        return Err(ContractError::Paused {});
+    }
+
     // Get farm info
-    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    let farm_info = FARM_INFO.load(deps.storage)?;
     // Get current phase index
     let current_phase_index = farm_info.current_phase_index;
 
     // Not allow to add reward balance to activated phase
     if phase_index <= current_phase_index && current_phase_index != 0 {
-        return Err(ContractError::Std(StdError::generic_err("Phase activated")));
+        return Err(ContractError::PhaseAlreadyActivated { phase_index });
     }
 
-    // Check the message sender is the whitelisted address
-    if farm_info.phases_info[phase_index as usize].whitelist != info.sender {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Unauthorized: Sender is not whitelisted address",
-        )));
+    // Not allow to add reward balance to a phase which does not exist
+    if phase_index >= farm_info.phases_count {
+        return Err(ContractError::PhaseNotFound { phase_index });
     }
 
-    // Get phase info in farm info
-    let phase_info = farm_info.phases_info[phase_index as usize].clone();
-    // Init response
-    let mut res = Response::new();
-    // Verify reward token asset
-    // Add reward balance to the phase
-    // When creating a new farm or adding a new phase, sender must add balance amount of reward_token
-    // Match reward token type:
-    // 1. If reward token is native token, sender must add balance amount of native token
-    //    to the new farm contract address by sending via funds when calling this msg.
-    // 2. If reward token is cw20 token, sender must add balance amount of cw20 token
-    //    to the new farm contract address by calling cw20 contract transfer_from method.
-    match farm_info.reward_token.clone() {
-        TokenInfo::Token { contract_addr } => {
-            let transfer = wasm_execute(
-                contract_addr.to_string(),
-                &Cw20ExecuteMsg::TransferFrom {
-                    owner: info.sender.to_string(),
-                    recipient: env.contract.address.to_string(),
-                    amount,
-                },
-                vec![],
-            )?;
-            res = res.add_message(transfer);
-        }
-        TokenInfo::NativeToken { denom } => {
-            // If reward token is native token, check the denom and amount of asset is valid
-            if !has_coins(&info.funds, &Coin { denom, amount }) {
-                return Err(ContractError::Std(StdError::generic_err(
-                    "Native token balance mismatch between the argument and the transferred",
-                )));
-            }
-        }
+    // Get phase info
+    let mut phase_info = PHASES_INFO.load(deps.storage, phase_index)?;
+
+    // Get reward token info of the phase
+    let reward_token_info = if let Some(reward_token_info) = phase_info
+        .reward_tokens
+        .iter_mut()
+        .find(|reward_token_info| reward_token_info.info == reward_token)
+    {
+        reward_token_info
+    } else {
+        return Err(ContractError::InvalidRewardToken {
+            token: reward_token.to_string(),
+            phase_index,
+        });
+    };
+
+    // Check the message sender is the whitelisted address of the reward token
+    if reward_token_info.whitelist != sender {
+        return Err(ContractError::NotWhitelisted { sender });
     }
 
     // Get current time
@@ -73,249 +148,665 @@ pub fn execute_add_reward_balance(
 
     // Not allow adding reward balance when current time is greater than start time of the phase
     if current_time > phase_info.start_time {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Current time is greater than start time of the phase",
-        )));
+        return Err(ContractError::PhaseStarted {
+            phase_index,
+            start_time: phase_info.start_time,
+        });
+    }
+
+    // Add reward balance to the phase
+    reward_token_info.reward_balance += amount;
+
+    // Save phase info
+    PHASES_INFO.save(deps.storage, phase_index, &phase_info)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "add_reward_balance")
+        .add_attribute("sender", sender)
+        .add_attribute("phase_index", phase_index.to_string())
+        .add_attribute("reward_token_asset", reward_token.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+pub fn execute_top_up_active_phase(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let fund = one_coin(&info)?;
+
+    // Check the amount of native token sent is equal to the argument
+    if fund.amount != amount {
+        return Err(ContractError::FundsMismatch {
+            expected: amount,
+            received: fund.amount,
+        });
+    }
+
+    let reward_token = TokenInfo::NativeToken { denom: fund.denom };
+
+    top_up_active_phase(deps, env, info.sender, reward_token, amount)
+}
+
+// Add reward balance that already arrived at the farm contract to the current phase
+// and re-derive the emission from the remaining reward balance
+fn top_up_active_phase(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    reward_token: TokenInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // Not allow adding reward balance when the farm is paused
+    if CONFIG.load(deps.storage)?.paused {
+        return Err(ContractError::Paused {});
     }
 
-    // Get the reward token balance of a phase in multiple phases.
-    let mut reward_balance = farm_info.phases_info[phase_index as usize].reward_balance;
+    // Get current time
+    let current_time = env.block.time.seconds();
+
+    // Get farm info
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+    let current_phase_index = farm_info.current_phase_index;
+    let total_weight = farm_info.total_weight();
+
+    // Get phase info of the current phase
+    let phase_info = phases.get(deps.storage, current_phase_index)?;
+
+    // Not allow topping up when the current phase has ended
+    if current_time >= phase_info.end_time {
+        return Err(ContractError::PhaseEnded {
+            phase_index: current_phase_index,
+            end_time: phase_info.end_time,
+        });
+    }
 
-    // Update reward balance
-    reward_balance += amount;
+    // Settle the current phase until the current time
+    phase_info.update_reward_ratio_and_time(current_time, total_weight);
+    phase_info.rebase_emission(current_time);
 
-    let new_phase_info = PhaseInfo {
-        reward_balance,
-        ..phase_info
+    // Get reward token info of the phase
+    let reward_token_info = if let Some(reward_token_info) = phase_info
+        .reward_tokens
+        .iter_mut()
+        .find(|reward_token_info| reward_token_info.info == reward_token)
+    {
+        reward_token_info
+    } else {
+        return Err(ContractError::InvalidRewardToken {
+            token: reward_token.to_string(),
+            phase_index: current_phase_index,
+        });
     };
 
-    // Save phase info to farm info in current phase index
-    farm_info.phases_info[phase_index as usize] = new_phase_info;
+    // Check the message sender is the whitelisted address of the reward token
+    if reward_token_info.whitelist != sender {
+        return Err(ContractError::NotWhitelisted { sender });
+    }
+
+    // Add reward balance to the phase
+    reward_token_info.reward_balance += amount;
+
+    // Save farm info and phases
     FARM_INFO.save(deps.storage, &farm_info)?;
+    phases.save(deps.storage)?;
 
-    Ok(res
-        .add_attribute("method", "add_reward_balance")
-        .add_attribute("sender", info.sender)
-        .add_attribute("phase_index", phase_index.to_string())
-        .add_attribute("reward_token_asset", farm_info.reward_token.to_string())
+    Ok(Response::new()
+        .add_attribute("method", "top_up_active_phase")
+        .add_attribute("sender", sender)
+        .add_attribute("phase_index", current_phase_index.to_string())
+        .add_attribute("reward_token_asset", reward_token.to_string())
         .add_attribute("amount", amount.to_string()))
 }
 
+pub fn execute_extend_phase(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_end_time: u64,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    // Check if the message sender is the owner of the contract
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Get current time
+    let current_time = env.block.time.seconds();
+
+    // Get farm info
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+    let current_phase_index = farm_info.current_phase_index;
+    let total_weight = farm_info.total_weight();
+
+    // Not allow overlapping the next phase
+    if current_phase_index + 1 < farm_info.phases_count {
+        let next_phase_info = phases.get(deps.storage, current_phase_index + 1)?;
+        if next_phase_info.start_time < new_end_time {
+            return Err(ContractError::PhaseOverlap {
+                new_start_time: next_phase_info.start_time,
+                end_time: new_end_time,
+            });
+        }
+    }
+
+    // Get phase info of the current phase
+    let phase_info = phases.get(deps.storage, current_phase_index)?;
+
+    // Not allow extending when the current phase has ended
+    if current_time >= phase_info.end_time {
+        return Err(ContractError::PhaseEnded {
+            phase_index: current_phase_index,
+            end_time: phase_info.end_time,
+        });
+    }
+
+    // Only allow extending the end time
+    if new_end_time <= phase_info.end_time {
+        return Err(ContractError::InvalidNewEndTime {
+            end_time: phase_info.end_time,
+            new_end_time,
+        });
+    }
+
+    // Settle the current phase until the current time
+    phase_info.update_reward_ratio_and_time(current_time, total_weight);
+    phase_info.rebase_emission(current_time);
+
+    let previous_end_time = phase_info.end_time;
+    phase_info.end_time = new_end_time;
+
+    // Save farm info and phases
+    FARM_INFO.save(deps.storage, &farm_info)?;
+    phases.save(deps.storage)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "extend_phase"),
+        ("phase_index", &current_phase_index.to_string()),
+        ("previous_end_time", &previous_end_time.to_string()),
+        ("new_end_time", &new_end_time.to_string()),
+    ]))
+}
+
 pub fn execute_remove_phase(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     phase_index: u64,
 ) -> Result<Response, ContractError> {
     // Get farm info
     let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, env.block.time.seconds())?;
     // Get current phase index
     let current_phase_index = farm_info.current_phase_index;
 
     // Not allow removing activated phase
     if phase_index <= current_phase_index {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Can not remove activated phase",
-        )));
+        return Err(ContractError::PhaseAlreadyActivated { phase_index });
+    }
+
+    // Not allow removing a phase which does not exist
+    if phase_index >= farm_info.phases_count {
+        return Err(ContractError::PhaseNotFound { phase_index });
     }
 
     // Get config
     let config: Config = CONFIG.load(deps.storage)?;
 
     // Check if the message sender is the owner of the contract
-    if config.farm_owner != info.sender {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Unauthorized: Only owner can remove phase",
-        )));
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
     }
 
     // Init response
     let mut res = Response::new();
-    // If phase already added reward balance, transfer back all phase reward balance to the sender
-    if farm_info.phases_info[phase_index as usize].reward_balance > Uint128::zero() {
-        // Get whitelist address
-        let whitelist = farm_info.phases_info[phase_index as usize]
-            .whitelist
-            .clone();
-        // Transfer reward balance to whitelist address
-        match farm_info.reward_token.clone() {
-            TokenInfo::Token { contract_addr } => {
-                let transfer_reward = wasm_execute(
-                    contract_addr.to_string(),
-                    &Cw20ExecuteMsg::Transfer {
-                        recipient: whitelist.to_string(),
-                        amount: farm_info.phases_info[phase_index as usize].reward_balance,
-                    },
-                    vec![],
-                )?;
-                res = res.add_message(transfer_reward);
-            }
-            TokenInfo::NativeToken { denom } => {
-                res = res.add_message(CosmosMsg::Bank(BankMsg::Send {
-                    to_address: whitelist.to_string(),
-                    amount: coins(
-                        farm_info.phases_info[phase_index as usize]
-                            .reward_balance
-                            .into(),
-                        denom,
-                    ),
-                }))
-            }
-        };
-        res = res.add_attribute(
-            "transfer_reward",
-            farm_info.phases_info[phase_index as usize]
-                .reward_balance
+    // If phase already added reward balance, transfer back all reward balance to the whitelist
+    for reward_token_info in phases
+        .get(deps.storage, phase_index)?
+        .reward_tokens
+        .iter()
+        .filter(|reward_token_info| !reward_token_info.reward_balance.is_zero())
+    {
+        res = res
+            .add_message(transfer_token_msg(
+                &reward_token_info.info,
+                &reward_token_info.whitelist,
+                reward_token_info.reward_balance,
+            )?)
+            .add_attribute(
+                "transfer_reward",
+                RewardTokenAsset {
+                    info: reward_token_info.info.clone(),
+                    amount: reward_token_info.reward_balance,
+                }
                 .to_string(),
-        );
+            );
     }
-    // Remove phase
-    farm_info.phases_info.remove(phase_index as usize);
-    // Save farm info
+    // Remove phase, the later phases are moved down by one index
+    for i in phase_index + 1..farm_info.phases_count {
+        let phase_info = phases.get(deps.storage, i)?.clone();
+        phases.insert(i - 1, phase_info);
+    }
+    farm_info.phases_count -= 1;
+    phases.remove(deps.storage, farm_info.phases_count);
+    // Save farm info and phases
     FARM_INFO.save(deps.storage, &farm_info)?;
+    phases.save(deps.storage)?;
 
     Ok(res
         .add_attribute("method", "remove_phase")
         .add_attribute("phase_index", phase_index.to_string()))
 }
 
+/// Maximum number of phases settled for a staker by a message,
+/// the staker idle across more phases settles them with `SettlePhases` first.
+pub const MAX_SETTLED_PHASES: u64 = 100;
+
 pub fn claim_all_reward(
+    storage: &dyn Storage,
+    farm_info: &mut FarmInfo,
+    phases: &mut Phases,
+    staker_info: &mut StakerInfo,
+    current_time: u64,
+) -> Result<Vec<RewardTokenAsset>, ContractError> {
+    accrue_all_reward(storage, farm_info, phases, staker_info, current_time)?;
+
+    let mut rewards: Vec<RewardTokenAsset> = vec![];
+    pay_unpaid_rewards(storage, phases, staker_info, &mut rewards)?;
+
+    // List the reward tokens of the current phase even if there is no reward
+    for reward_token_info in phases
+        .get(storage, farm_info.current_phase_index)?
+        .reward_tokens
+        .iter()
+    {
+        add_reward_amount(&mut rewards, &reward_token_info.info, Uint128::zero());
+    }
+
+    Ok(rewards)
+}
+
+// Accrue the reward of the staker until the current time to the unpaid rewards,
+// the reward debt of the current phase is updated to the accrued reward
+fn accrue_all_reward(
+    storage: &dyn Storage,
     farm_info: &mut FarmInfo,
+    phases: &mut Phases,
     staker_info: &mut StakerInfo,
     current_time: u64,
-) -> Uint128 {
-    let mut reward_amount = Uint128::zero();
+) -> Result<(), ContractError> {
     let &current_phase_index = &farm_info.current_phase_index;
 
-    // If staker has joined previous phases, loops all farm info to get reward per second from current phase index to staker joined phases
-    for i in staker_info.joined_phase..current_phase_index {
-        // Get accrued token per share
-        let accrued_token_per_share = farm_info.phases_info[i as usize].accrued_token_per_share;
+    // Not allow settling more phases than the maximum in a message
+    if current_phase_index - staker_info.joined_phase > MAX_SETTLED_PHASES {
+        return Err(ContractError::TooManyPhasesToSettle {
+            joined_phase: staker_info.joined_phase,
+            current_phase_index,
+        });
+    }
 
-        // Calculate reward amount
-        reward_amount += calc_reward_amount(
-            staker_info.amount,
-            accrued_token_per_share,
-            staker_info.reward_debt[i as usize],
-        );
-        // Update staker info
-        staker_info.reward_debt[i as usize] = staker_info.amount * accrued_token_per_share;
-        // Increase length of user reward debt to current phase index
-        staker_info.reward_debt.push(Uint128::zero());
+    release_expired_locks(
+        storage,
+        phases,
+        staker_info,
+        current_phase_index,
+        current_time,
+    )?;
+
+    // If staker has joined previous phases, settle the reward of the phases from staker joined phase to current phase index
+    settle_phases(storage, phases, staker_info, current_phase_index)?;
+
+    let total_weight = farm_info.total_weight();
+    let phase_info = phases.get(storage, current_phase_index)?;
+
+    phase_info.update_reward_ratio_and_time(current_time, total_weight);
+
+    let reward_amounts = phase_info
+        .reward_tokens
+        .iter()
+        .enumerate()
+        .map(|(j, reward_token_info)| {
+            calc_reward_amount(
+                staker_info.weight(),
+                reward_token_info.accrued_token_per_share,
+                staker_info.reward_debt[j],
+            )
+        })
+        .collect::<StdResult<_>>()?;
+    staker_info.add_unpaid_reward(current_phase_index, reward_amounts);
+    staker_info.reward_debt = phase_info
+        .accrued_token_per_share()
+        .into_iter()
+        .map(|accrued_token_per_share| {
+            calc_accrued_reward(staker_info.weight(), accrued_token_per_share)
+        })
+        .collect();
+
+    Ok(())
+}
+
+// Move the unpaid rewards of the staker to the list of rewards to be transferred
+fn pay_unpaid_rewards(
+    storage: &dyn Storage,
+    phases: &mut Phases,
+    staker_info: &mut StakerInfo,
+    rewards: &mut Vec<RewardTokenAsset>,
+) -> StdResult<()> {
+    for unpaid_reward in staker_info.unpaid_rewards.drain(..) {
+        let phase_info = phases.get(storage, unpaid_reward.phase_index)?;
+        for (reward_token_info, amount) in phase_info
+            .reward_tokens
+            .iter_mut()
+            .zip(unpaid_reward.amounts)
+        {
+            add_reward_amount(rewards, &reward_token_info.info, amount);
+            reward_token_info.claimed_reward += amount;
+        }
     }
 
-    let phase_info = &mut farm_info.phases_info[current_phase_index as usize];
-    let staked_token_balance = farm_info.staked_token_balance;
+    Ok(())
+}
+
+// Accrue the boosted reward of the expired locks of the staker until their unlock time
+// to the unpaid rewards and release their boost weight, in time order.
+// Only the locks expired before the end of the given phase are released
+fn release_expired_locks(
+    storage: &dyn Storage,
+    phases: &mut Phases,
+    staker_info: &mut StakerInfo,
+    up_to: u64,
+    current_time: u64,
+) -> StdResult<()> {
+    for lock in staker_info.take_expired_locks(current_time) {
+        let boost_checkpoint = phases.boost_checkpoint(storage, lock.unlock_time)?;
+        let phase_index = boost_checkpoint.phase_index;
+        if phase_index > up_to {
+            staker_info.locks.push(lock);
+            continue;
+        }
 
-    phase_info.update_reward_ratio_and_time(current_time, staked_token_balance);
+        // The phases before the unlock time are settled with the boosted weight
+        settle_phases(storage, phases, staker_info, phase_index)?;
+
+        let reward_amounts = boost_checkpoint
+            .accrued_token_per_share
+            .iter()
+            .zip(staker_info.reward_debt.iter())
+            .map(|(accrued_token_per_share, reward_debt)| {
+                calc_reward_amount(staker_info.weight(), *accrued_token_per_share, *reward_debt)
+            })
+            .collect::<StdResult<_>>()?;
+        staker_info.add_unpaid_reward(phase_index, reward_amounts);
+
+        staker_info.boost_weight -= lock.boost_weight();
+        staker_info.reward_debt = boost_checkpoint
+            .accrued_token_per_share
+            .into_iter()
+            .map(|accrued_token_per_share| {
+                calc_accrued_reward(staker_info.weight(), accrued_token_per_share)
+            })
+            .collect();
+        phases.release_boost_checkpoint(storage, lock.unlock_time)?;
+    }
 
-    reward_amount += calc_reward_amount(
-        staker_info.amount,
-        phase_info.accrued_token_per_share,
-        staker_info.reward_debt[current_phase_index as usize],
-    );
+    Ok(())
+}
+
+// Settle the reward of the ended phases from the joined phase of the staker up to the given phase
+// to the unpaid rewards, the staker joins the given phase with zero reward debt
+fn settle_phases(
+    storage: &dyn Storage,
+    phases: &mut Phases,
+    staker_info: &mut StakerInfo,
+    up_to: u64,
+) -> StdResult<()> {
+    for i in staker_info.joined_phase..up_to {
+        let reward_amounts = phases
+            .get(storage, i)?
+            .reward_tokens
+            .iter()
+            .enumerate()
+            .map(|(j, reward_token_info)| {
+                calc_reward_amount(
+                    staker_info.weight(),
+                    reward_token_info.accrued_token_per_share,
+                    staker_info.reward_debt[j],
+                )
+            })
+            .collect::<StdResult<_>>()?;
+        staker_info.add_unpaid_reward(i, reward_amounts);
+        // The staker joins the next phase with zero reward debt
+        staker_info.reward_debt =
+            vec![Uint256::zero(); phases.get(storage, i + 1)?.reward_tokens.len()];
+    }
+    staker_info.joined_phase = up_to;
 
-    reward_amount
+    Ok(())
 }
 
-pub fn execute_deposit(
+// Add reward amount of a reward token to the list of rewards
+fn add_reward_amount(rewards: &mut Vec<RewardTokenAsset>, info: &TokenInfo, amount: Uint128) {
+    if let Some(reward) = rewards.iter_mut().find(|reward| &reward.info == info) {
+        reward.amount += amount;
+    } else {
+        rewards.push(RewardTokenAsset {
+            info: info.clone(),
+            amount,
+        });
+    }
+}
+
+// Create messages to transfer all non-zero rewards to the recipient
+fn transfer_rewards_msgs(
+    rewards: &[RewardTokenAsset],
+    recipient: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    rewards
+        .iter()
+        .filter(|reward| !reward.amount.is_zero())
+        .map(|reward| transfer_token_msg(&reward.info, recipient, reward.amount))
+        .collect()
+}
+
+// Create message to transfer cw20 or native token from the farm contract
+fn transfer_token_msg(
+    token: &TokenInfo,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    match token {
+        TokenInfo::Token { contract_addr } => Ok(wasm_execute(
+            contract_addr.to_string(),
+            &Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            },
+            vec![],
+        )?
+        .into()),
+        TokenInfo::NativeToken { denom } => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.into(), denom),
+        })),
+    }
+}
+
+// Format rewards as a response attribute value
+fn rewards_to_string(rewards: &[RewardTokenAsset]) -> String {
+    rewards
+        .iter()
+        .map(|reward| reward.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// Deposit native staked token sent via funds
+pub fn execute_deposit_native(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    lock_duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    let farm_info = FARM_INFO.load(deps.storage)?;
+
+    // Only native staked token can be deposited via funds
+    let amount = match farm_info.staked_token {
+        TokenInfo::Token { .. } => {
+            return Err(ContractError::Cw20StakedToken {});
+        }
+        TokenInfo::NativeToken { denom } => must_pay(&info, &denom)?,
+    };
+
+    execute_deposit(deps, env, info.sender, amount, lock_duration)
+}
+
+pub fn execute_deposit(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
     amount: Uint128,
+    lock_duration: Option<u64>,
 ) -> Result<Response, ContractError> {
     // Not allow depositing 0 amount
     if amount.is_zero() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "InvalidZeroAmount: Deposit amount is 0",
-        )));
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    // Not allow depositing when the farm is paused
+    if config.paused {
+        return Err(ContractError::Paused {});
     }
 
+    // The lock duration must be one of the lock tiers
+    let lock_tier = match lock_duration {
+        Some(duration) => Some(
+            config
+                .lock_tiers
+                .iter()
+                .find(|lock_tier| lock_tier.duration == duration)
+                .ok_or(ContractError::InvalidLockDuration { duration })?,
+        ),
+        None => None,
+    };
+
+    let current_time = env.block.time.seconds();
     let farm_info = &mut FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
     let current_phase_index: usize = farm_info.current_phase_index as usize;
 
+    let phase_info = phases.get(deps.storage, current_phase_index as u64)?;
+
     // Not allow depositing if reward token is not added to the phase yet
-    if farm_info.phases_info[current_phase_index].reward_balance == Uint128::zero() {
-        return Err(ContractError::Std(StdError::generic_err("Empty phase")));
+    if phase_info
+        .reward_tokens
+        .iter()
+        .all(|reward_token_info| reward_token_info.reward_balance.is_zero())
+    {
+        return Err(ContractError::EmptyPhase {
+            phase_index: current_phase_index as u64,
+        });
+    }
+
+    // Not allow depositing when current time is greater than end time of the phase
+    if current_time > phase_info.end_time {
+        return Err(ContractError::PhaseEnded {
+            phase_index: current_phase_index as u64,
+            end_time: phase_info.end_time,
+        });
     }
 
+    let reward_debt = vec![Uint256::zero(); phase_info.reward_tokens.len()];
+
     let mut staker_info = STAKERS_INFO
-        .load(deps.storage, info.sender.clone())
+        .load(deps.storage, sender.clone())
         .unwrap_or(StakerInfo {
             amount: Uint128::zero(),
-            reward_debt: vec![Uint128::zero(); current_phase_index + 1],
+            reward_debt,
             joined_phase: current_phase_index as u64,
+            locks: vec![],
+            boost_weight: Uint128::zero(),
+            unpaid_rewards: vec![],
         });
 
-    let current_time = env.block.time.seconds();
-    // Not allow depositing when current time is greater than end time of the phase
-    if current_time > farm_info.phases_info[current_phase_index].end_time {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Current time is not in the range of the phase",
-        )));
-    }
-
     // Check phase limit per user
     if let Some(phases_limit_per_user) = farm_info.phases_limit_per_user {
         if staker_info.amount + amount > phases_limit_per_user {
-            return Err(ContractError::Std(StdError::generic_err(
-                "Deposit amount exceeds phase limit per user",
-            )));
+            return Err(ContractError::ExceedsUserLimit {
+                limit: phases_limit_per_user,
+                requested: staker_info.amount + amount,
+            });
         }
     }
 
-    let mut res = Response::new();
-    let reward_amount = claim_all_reward(farm_info, &mut staker_info, current_time);
-
-    // If reward amount is greater than 0, transfer reward amount to staker
-    if reward_amount > Uint128::zero() {
-        match farm_info.reward_token.clone() {
-            TokenInfo::Token { contract_addr } => {
-                let transfer_reward = wasm_execute(
-                    contract_addr.to_string(),
-                    &Cw20ExecuteMsg::Transfer {
-                        recipient: info.sender.to_string(),
-                        amount: reward_amount,
-                    },
-                    vec![],
-                )?;
-                res = res.add_message(transfer_reward);
-            }
-            TokenInfo::NativeToken { denom } => {
-                res = res.add_message(CosmosMsg::Bank(BankMsg::Send {
-                    to_address: info.sender.to_string(),
-                    amount: coins(reward_amount.into(), denom),
-                }))
-            }
-        };
+    // Check max total staked of the farm
+    if let Some(max_total_staked) = config.max_total_staked {
+        if farm_info.staked_token_balance + amount > max_total_staked {
+            return Err(ContractError::ExceedsMaxTotalStaked {
+                limit: max_total_staked,
+                requested: farm_info.staked_token_balance + amount,
+            });
+        }
     }
 
-    // Deposit staked token to the farm contract
-    let transfer = wasm_execute(
-        farm_info.staked_token.to_string(),
-        &Cw20ExecuteMsg::TransferFrom {
-            owner: info.sender.to_string(),
-            recipient: env.contract.address.to_string(),
-            amount,
-        },
-        vec![],
+    let rewards = claim_all_reward(
+        deps.storage,
+        farm_info,
+        &mut phases,
+        &mut staker_info,
+        current_time,
     )?;
 
+    // If reward amount is greater than 0, transfer reward amount to staker
+    let res = Response::new().add_messages(transfer_rewards_msgs(&rewards, &sender)?);
+
+    // Staked token is already transferred to the farm contract by cw20 send or via funds
     farm_info.staked_token_balance += amount;
 
     staker_info.amount += amount;
-    staker_info.reward_debt[current_phase_index] =
-        staker_info.amount * farm_info.phases_info[current_phase_index].accrued_token_per_share;
+    // Lock the deposited amount with the multiplier of the lock tier
+    if let Some(lock_tier) = lock_tier {
+        let lock = Lock {
+            amount,
+            multiplier: lock_tier.multiplier,
+            unlock_time: current_time + lock_tier.duration,
+        };
+        // The boost weight of the lock expires at its unlock time
+        phases.add_boost_expiry(deps.storage, lock.unlock_time, lock.boost_weight())?;
+        farm_info.boost_weight += lock.boost_weight();
+        staker_info.boost_weight += lock.boost_weight();
+        staker_info.locks.push(lock);
+    }
+    staker_info.reward_debt = phases
+        .get(deps.storage, current_phase_index as u64)?
+        .accrued_token_per_share()
+        .into_iter()
+        .map(|accrued_token_per_share| {
+            calc_accrued_reward(staker_info.weight(), accrued_token_per_share)
+        })
+        .collect();
     staker_info.joined_phase = current_phase_index as u64;
 
     FARM_INFO.save(deps.storage, farm_info)?;
-    STAKERS_INFO.save(deps.storage, info.sender, &staker_info)?;
+    phases.save(deps.storage)?;
+    STAKERS_INFO.save(deps.storage, sender, &staker_info)?;
 
-    res = res
-        .add_message(transfer)
+    let res = res
         .add_attribute("current_time", current_time.to_string())
         .add_attribute("method", "deposit")
         .add_attribute("deposit_amount", amount.to_string())
-        .add_attribute("harvest_reward_amount", reward_amount.to_string());
+        .add_attribute("harvest_reward_amount", rewards_to_string(&rewards));
 
     Ok(res)
 }
@@ -325,78 +816,105 @@ pub fn execute_withdraw(
     env: Env,
     info: MessageInfo,
     amount: Uint128,
+    recipient: Option<String>,
 ) -> Result<Response, ContractError> {
     if amount.is_zero() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "InvalidZeroAmount: Withdraw amount is 0",
-        )));
+        return Err(ContractError::InvalidZeroAmount {});
     }
 
+    // The staked tokens and reward tokens are sent to the sender by default
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    let current_time = env.block.time.seconds();
     let farm_info = &mut FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
     let current_phase_index: usize = farm_info.current_phase_index as usize;
     let mut staker_info =
         if let Some(staker_info) = STAKERS_INFO.may_load(deps.storage, info.sender.clone())? {
             staker_info
         } else {
-            return Err(ContractError::Std(StdError::generic_err(
-                "Unauthorized: Sender is not staker",
-            )));
+            return Err(ContractError::NotStaker {
+                address: info.sender,
+            });
         };
 
     if staker_info.amount < amount {
-        return Err(ContractError::Std(StdError::generic_err(
-            "InsufficientFunds: Withdraw amount exceeds staked amount",
-        )));
+        return Err(ContractError::InsufficientStake {
+            staked: staker_info.amount,
+            requested: amount,
+        });
     }
 
-    let mut res = Response::new();
-    let current_time = env.block.time.seconds();
-
-    let reward_amount = claim_all_reward(farm_info, &mut staker_info, current_time);
-
-    // If reward amount is greater than 0, transfer reward token to the sender
-    if reward_amount > Uint128::zero() {
-        match farm_info.reward_token.clone() {
-            TokenInfo::Token { contract_addr } => {
-                let transfer_reward = wasm_execute(
-                    contract_addr.to_string(),
-                    &Cw20ExecuteMsg::Transfer {
-                        recipient: info.sender.to_string(),
-                        amount: reward_amount,
-                    },
-                    vec![],
-                )?;
-                res = res.add_message(transfer_reward);
-            }
-            TokenInfo::NativeToken { denom } => {
-                res = res.add_message(CosmosMsg::Bank(BankMsg::Send {
-                    to_address: info.sender.to_string(),
-                    amount: coins(reward_amount.into(), denom),
-                }))
-            }
-        };
+    // Not allow withdrawing the locked amount before the unlock time
+    let unlocked_amount = staker_info.amount - staker_info.locked_amount(current_time);
+    if unlocked_amount < amount {
+        return Err(ContractError::LockedStake {
+            unlocked: unlocked_amount,
+            requested: amount,
+        });
     }
 
-    // Withdraw staked token from the farm contract by using cw20 transfer message
-    let withdraw = wasm_execute(
-        farm_info.staked_token.to_string(),
-        &Cw20ExecuteMsg::Transfer {
-            recipient: info.sender.to_string(),
+    // The reward is not paid while the farm is paused,
+    // it is kept as unpaid reward of the staker to be claimed after unpausing
+    let rewards = if CONFIG.load(deps.storage)?.paused {
+        accrue_all_reward(
+            deps.storage,
+            farm_info,
+            &mut phases,
+            &mut staker_info,
+            current_time,
+        )?;
+        phases
+            .get(deps.storage, current_phase_index as u64)?
+            .reward_tokens
+            .iter()
+            .map(|reward_token_info| RewardTokenAsset {
+                info: reward_token_info.info.clone(),
+                amount: Uint128::zero(),
+            })
+            .collect()
+    } else {
+        claim_all_reward(
+            deps.storage,
+            farm_info,
+            &mut phases,
+            &mut staker_info,
+            current_time,
+        )?
+    };
+
+    // If reward amount is greater than 0, transfer reward token to the recipient
+    let res = Response::new()
+        .add_messages(transfer_rewards_msgs(&rewards, &recipient)?)
+        // Withdraw staked token from the farm contract
+        .add_message(transfer_token_msg(
+            &farm_info.staked_token,
+            &recipient,
             amount,
-        },
-        vec![],
-    )?;
+        )?);
+
     // Decrease staked token balance
     farm_info.staked_token_balance -= amount;
 
     // Update staker amount
     staker_info.amount -= amount;
-    staker_info.reward_debt[current_phase_index] =
-        staker_info.amount * farm_info.phases_info[current_phase_index].accrued_token_per_share;
+    staker_info.reward_debt = phases
+        .get(deps.storage, current_phase_index as u64)?
+        .accrued_token_per_share()
+        .into_iter()
+        .map(|accrued_token_per_share| {
+            calc_accrued_reward(staker_info.weight(), accrued_token_per_share)
+        })
+        .collect();
     staker_info.joined_phase = current_phase_index as u64;
 
-    // Check if staker amount is zero, remove staker info from storage
-    if staker_info.amount == Uint128::zero() {
+    // Check if staker amount is zero and there is no unpaid reward, remove staker info from storage
+    if staker_info.amount == Uint128::zero() && staker_info.unpaid_rewards.is_empty() {
         STAKERS_INFO.remove(deps.storage, info.sender);
     } else {
         // Update staker info
@@ -404,78 +922,382 @@ pub fn execute_withdraw(
     }
     // Save farm info
     FARM_INFO.save(deps.storage, farm_info)?;
+    phases.save(deps.storage)?;
 
-    res = res
-        .add_message(withdraw)
+    let res = res
         .add_attribute("method", "withdraw")
+        .add_attribute("recipient", recipient)
         .add_attribute("withdraw_amount", amount.to_string())
-        .add_attribute("harvest_reward_amount", reward_amount.to_string())
+        .add_attribute("harvest_reward_amount", rewards_to_string(&rewards))
         .add_attribute("current_time", current_time.to_string());
 
     Ok(res)
 }
 
-// Harvest reward token from the farm contract to the sender
-pub fn execute_harvest(
+// Withdraw all staked token of the sender without paying any reward
+pub fn execute_emergency_withdraw(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    let mut staker_info =
+    let staker_info =
         if let Some(staker_info) = STAKERS_INFO.may_load(deps.storage, info.sender.clone())? {
             staker_info
         } else {
-            return Err(ContractError::Std(StdError::generic_err(
-                "Unauthorized: Only staker can harvest reward",
-            )));
+            return Err(ContractError::NotStaker {
+                address: info.sender,
+            });
         };
     let farm_info = &mut FARM_INFO.load(deps.storage)?;
 
     let current_time = env.block.time.seconds();
-    let current_phase_index: usize = farm_info.current_phase_index as usize;
-
-    let reward_amount = claim_all_reward(farm_info, &mut staker_info, current_time);
+
+    // Not allow withdrawing the locked amount before the unlock time
+    let locked_amount = staker_info.locked_amount(current_time);
+    if !locked_amount.is_zero() {
+        return Err(ContractError::LockedStake {
+            unlocked: staker_info.amount - locked_amount,
+            requested: staker_info.amount,
+        });
+    }
+
+    // No reward is computed, only the staked token balance and the weight are decreased
+    // by the whole stake of the sender. The reward emitted since the last update
+    // is distributed to the remaining stakers
+    let mut phases = Phases::default();
+    farm_info.staked_token_balance -= staker_info.amount;
+    for lock in staker_info.locks.iter() {
+        // The boost weight of the lock is still in the farm until its expiry is processed
+        if phases.remove_boost_expiry(deps.storage, lock.unlock_time, lock.boost_weight())? {
+            farm_info.boost_weight -= lock.boost_weight();
+        } else {
+            phases.release_boost_checkpoint(deps.storage, lock.unlock_time)?;
+        }
+    }
+
+    // The unpaid reward of the sender goes back to the undistributed reward of its phase,
+    // the other pending reward of the sender is forfeited with the staker info
+    for unpaid_reward in staker_info.unpaid_rewards.iter() {
+        let phase_info = phases.get(deps.storage, unpaid_reward.phase_index)?;
+        for (reward_token_info, amount) in phase_info
+            .reward_tokens
+            .iter_mut()
+            .zip(unpaid_reward.amounts.iter())
+        {
+            reward_token_info.distributed_reward -= *amount;
+            reward_token_info.undistributed_reward += *amount;
+        }
+    }
+    STAKERS_INFO.remove(deps.storage, info.sender.clone());
+    FARM_INFO.save(deps.storage, farm_info)?;
+    phases.save(deps.storage)?;
+
+    Ok(Response::new()
+        .add_message(transfer_token_msg(
+            &farm_info.staked_token,
+            &info.sender,
+            staker_info.amount,
+        )?)
+        .add_event(Event::new("emergency_withdraw").add_attributes([
+            ("staker", info.sender.as_str()),
+            ("amount", &staker_info.amount.to_string()),
+        ]))
+        .add_attribute("method", "emergency_withdraw")
+        .add_attribute("withdraw_amount", staker_info.amount.to_string())
+        .add_attribute("current_time", current_time.to_string()))
+}
+
+// Harvest reward token from the farm contract to the recipient (the sender by default)
+pub fn execute_harvest(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    harvest(deps, env, info.sender, recipient, "harvest")
+}
+
+// Harvest reward token of a staker by an approved operator
+// to the recipient approved by the staker
+pub fn execute_harvest_for(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staker: String,
+) -> Result<Response, ContractError> {
+    let staker = deps.api.addr_validate(&staker)?;
+
+    let recipient = if let Some(recipient) =
+        OPERATORS.may_load(deps.storage, (staker.clone(), info.sender.clone()))?
+    {
+        recipient
+    } else {
+        return Err(ContractError::OperatorNotApproved {
+            operator: info.sender,
+            staker,
+        });
+    };
+
+    harvest(deps, env, staker, recipient, "harvest_for")
+}
+
+fn harvest(
+    deps: DepsMut,
+    env: Env,
+    staker: Addr,
+    recipient: Addr,
+    method: &str,
+) -> Result<Response, ContractError> {
+    // Not allow harvesting when the farm is paused
+    if CONFIG.load(deps.storage)?.paused {
+        return Err(ContractError::Paused {});
+    }
+
+    let mut staker_info =
+        if let Some(staker_info) = STAKERS_INFO.may_load(deps.storage, staker.clone())? {
+            staker_info
+        } else {
+            return Err(ContractError::NotStaker { address: staker });
+        };
+    let farm_info = &mut FARM_INFO.load(deps.storage)?;
+
+    let current_time = env.block.time.seconds();
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+    let current_phase_index: usize = farm_info.current_phase_index as usize;
+
+    let rewards = claim_all_reward(
+        deps.storage,
+        farm_info,
+        &mut phases,
+        &mut staker_info,
+        current_time,
+    )?;
 
     // Check if there is any reward to harvest
-    if reward_amount == Uint128::zero() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "InsufficientFunds: Reward amount is zero",
-        )));
+    if rewards.iter().all(|reward| reward.amount.is_zero()) {
+        return Err(ContractError::NoReward {});
     }
 
-    staker_info.reward_debt[current_phase_index] =
-        staker_info.amount * farm_info.phases_info[current_phase_index].accrued_token_per_share;
+    staker_info.reward_debt = phases
+        .get(deps.storage, current_phase_index as u64)?
+        .accrued_token_per_share()
+        .into_iter()
+        .map(|accrued_token_per_share| {
+            calc_accrued_reward(staker_info.weight(), accrued_token_per_share)
+        })
+        .collect();
     staker_info.joined_phase = current_phase_index as u64;
 
-    STAKERS_INFO.save(deps.storage, info.sender.clone(), &staker_info)?;
+    // The unpaid reward of a staker who withdrew everything while paused is harvested
+    if staker_info.amount.is_zero() {
+        STAKERS_INFO.remove(deps.storage, staker.clone());
+    } else {
+        STAKERS_INFO.save(deps.storage, staker.clone(), &staker_info)?;
+    }
     FARM_INFO.save(deps.storage, farm_info)?;
-    // Init response
-    let mut res = Response::new();
+    phases.save(deps.storage)?;
+
+    // Transfer all reward tokens to the recipient
+    let res = Response::new()
+        .add_messages(transfer_rewards_msgs(&rewards, &recipient)?)
+        .add_attribute("method", method)
+        .add_attribute("staker", staker)
+        .add_attribute("recipient", recipient)
+        .add_attribute("reward_amount", rewards_to_string(&rewards))
+        .add_attribute("current_time", current_time.to_string());
+
+    Ok(res)
+}
+
+// Settle the reward of the ended phases up to the given phase and transfer it to the sender,
+// so a staker who has been idle across many phases can settle them in several messages.
+// The reward is kept as unpaid reward of the staker while the farm is paused
+pub fn execute_settle_phases(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    up_to: u64,
+) -> Result<Response, ContractError> {
+    let mut staker_info =
+        if let Some(staker_info) = STAKERS_INFO.may_load(deps.storage, info.sender.clone())? {
+            staker_info
+        } else {
+            return Err(ContractError::NotStaker {
+                address: info.sender,
+            });
+        };
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+
+    let current_time = env.block.time.seconds();
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+
+    // Only the ended phases which are not settled yet can be settled
+    if up_to <= staker_info.joined_phase || up_to > farm_info.current_phase_index {
+        return Err(ContractError::InvalidSettlePhases {
+            up_to,
+            joined_phase: staker_info.joined_phase,
+            current_phase_index: farm_info.current_phase_index,
+        });
+    }
+
+    let mut rewards = vec![];
+    release_expired_locks(
+        deps.storage,
+        &mut phases,
+        &mut staker_info,
+        up_to,
+        current_time,
+    )?;
+    settle_phases(deps.storage, &mut phases, &mut staker_info, up_to)?;
+    if CONFIG.load(deps.storage)?.paused {
+        // Nothing is transferred while paused
+        for unpaid_reward in staker_info.unpaid_rewards.iter() {
+            for reward_token_info in phases
+                .get(deps.storage, unpaid_reward.phase_index)?
+                .reward_tokens
+                .iter()
+            {
+                add_reward_amount(&mut rewards, &reward_token_info.info, Uint128::zero());
+            }
+        }
+    } else {
+        pay_unpaid_rewards(deps.storage, &mut phases, &mut staker_info, &mut rewards)?;
+    }
+
+    STAKERS_INFO.save(deps.storage, info.sender.clone(), &staker_info)?;
+    FARM_INFO.save(deps.storage, &farm_info)?;
+    phases.save(deps.storage)?;
+
+    // Transfer the reward tokens of the settled phases to the sender
+    let res = Response::new()
+        .add_messages(transfer_rewards_msgs(&rewards, &info.sender)?)
+        .add_attribute("method", "settle_phases")
+        .add_attribute("staker", info.sender)
+        .add_attribute("up_to", up_to.to_string())
+        .add_attribute("reward_amount", rewards_to_string(&rewards))
+        .add_attribute("current_time", current_time.to_string());
+
+    Ok(res)
+}
+
+// Add the reward of the staked token to the staked amount of the sender
+// and transfer the other reward tokens to the sender
+pub fn execute_compound(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    // Not allow compounding when the farm is paused
+    if config.paused {
+        return Err(ContractError::Paused {});
+    }
+
+    let mut staker_info =
+        if let Some(staker_info) = STAKERS_INFO.may_load(deps.storage, info.sender.clone())? {
+            staker_info
+        } else {
+            return Err(ContractError::NotStaker {
+                address: info.sender,
+            });
+        };
+    let farm_info = &mut FARM_INFO.load(deps.storage)?;
+
+    let current_time = env.block.time.seconds();
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+    let current_phase_index: usize = farm_info.current_phase_index as usize;
+
+    // Only allow compounding when the staked token is a reward token of the current phase
+    if !phases
+        .get(deps.storage, current_phase_index as u64)?
+        .reward_tokens
+        .iter()
+        .any(|reward_token_info| reward_token_info.info == farm_info.staked_token)
+    {
+        return Err(ContractError::CompoundNotSupported {
+            token: farm_info.staked_token.to_string(),
+        });
+    }
+
+    let rewards = claim_all_reward(
+        deps.storage,
+        farm_info,
+        &mut phases,
+        &mut staker_info,
+        current_time,
+    )?;
+
+    // Check if there is any reward to compound or harvest
+    if rewards.iter().all(|reward| reward.amount.is_zero()) {
+        return Err(ContractError::NoReward {});
+    }
 
-    // Transfer reward token to the sender
-    match &farm_info.reward_token {
-        TokenInfo::Token { contract_addr } => {
-            let transfer_reward = wasm_execute(
-                contract_addr.to_string(),
-                &Cw20ExecuteMsg::Transfer {
-                    recipient: info.sender.to_string(),
-                    amount: reward_amount,
-                },
-                vec![],
-            )?;
-            res = res.add_message(transfer_reward);
+    let reward_amount = rewards_to_string(&rewards);
+
+    // The reward of the staked token is already in the farm contract
+    let (compounded_rewards, harvested_rewards): (Vec<RewardTokenAsset>, Vec<RewardTokenAsset>) =
+        rewards
+            .into_iter()
+            .partition(|reward| reward.info == farm_info.staked_token);
+    let compound_amount = compounded_rewards
+        .iter()
+        .map(|reward| reward.amount)
+        .sum::<Uint128>();
+
+    // Check phase limit per user
+    if let Some(phases_limit_per_user) = farm_info.phases_limit_per_user {
+        if staker_info.amount + compound_amount > phases_limit_per_user {
+            return Err(ContractError::ExceedsUserLimit {
+                limit: phases_limit_per_user,
+                requested: staker_info.amount + compound_amount,
+            });
         }
-        TokenInfo::NativeToken { denom } => {
-            res = res.add_message(CosmosMsg::Bank(BankMsg::Send {
-                to_address: info.sender.to_string(),
-                amount: coins(reward_amount.into(), denom),
-            }))
+    }
+
+    // Check max total staked of the farm
+    if let Some(max_total_staked) = config.max_total_staked {
+        if farm_info.staked_token_balance + compound_amount > max_total_staked {
+            return Err(ContractError::ExceedsMaxTotalStaked {
+                limit: max_total_staked,
+                requested: farm_info.staked_token_balance + compound_amount,
+            });
         }
-    };
+    }
 
-    let res = res
-        .add_attribute("method", "harvest")
-        .add_attribute("reward_amount", reward_amount.to_string())
+    farm_info.staked_token_balance += compound_amount;
+
+    staker_info.amount += compound_amount;
+    staker_info.reward_debt = phases
+        .get(deps.storage, current_phase_index as u64)?
+        .accrued_token_per_share()
+        .into_iter()
+        .map(|accrued_token_per_share| {
+            calc_accrued_reward(staker_info.weight(), accrued_token_per_share)
+        })
+        .collect();
+    staker_info.joined_phase = current_phase_index as u64;
+
+    STAKERS_INFO.save(deps.storage, info.sender.clone(), &staker_info)?;
+    FARM_INFO.save(deps.storage, farm_info)?;
+    phases.save(deps.storage)?;
+
+    // Transfer the other reward tokens to the sender
+    let res = Response::new()
+        .add_messages(transfer_rewards_msgs(&harvested_rewards, &info.sender)?)
+        .add_attribute("method", "compound")
+        .add_attribute("compound_amount", compound_amount.to_string())
+        .add_attribute("reward_amount", reward_amount)
         .add_attribute("current_time", current_time.to_string());
 
     Ok(res)
@@ -487,55 +1309,82 @@ pub fn execute_add_phase(
     info: MessageInfo,
     new_start_time: u64,
     new_end_time: u64,
-    whitelist: Addr,
+    reward_tokens: Vec<PhaseRewardToken>,
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     // Check if the message sender is the owner of the contract
-    if config.farm_owner != info.sender {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Unauthorized: Only owner can add new phase",
-        )));
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
     }
 
+    let current_time = env.block.time.seconds();
+
     // Validate time range
     validate_time_range(env, new_start_time, new_end_time)?;
 
+    // Validate reward tokens
+    validate_reward_tokens(deps.api, &reward_tokens)?;
+
     let mut farm_info: FarmInfo = FARM_INFO.load(deps.storage)?;
-    let phases_length = farm_info.phases_info.len();
-    let current_phase_index: usize = farm_info.current_phase_index as usize;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+    let current_phase_index = farm_info.current_phase_index;
+    let end_time = phases.get(deps.storage, current_phase_index)?.end_time;
 
     // Not allow add new phase when new start time is less than end time of the current phase
-    if new_start_time < farm_info.phases_info[current_phase_index].end_time {
-        return Err(ContractError::Std(StdError::generic_err(
-            "New start time is less than end time of the current phase",
-        )));
+    if new_start_time < end_time {
+        return Err(ContractError::PhaseOverlap {
+            new_start_time,
+            end_time,
+        });
     }
 
     // Not allow add new phase when previous phase is not active yet
-    if phases_length - 1 > current_phase_index {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Previous phase is not active",
-        )));
-    }
-
-    // Increase length of farm info
-    farm_info.phases_info.push(PhaseInfo {
-        start_time: new_start_time,
-        end_time: new_end_time,
-        whitelist: whitelist.clone(),
-        reward_balance: Uint128::zero(),
-        last_reward_time: new_start_time,
-        accrued_token_per_share: Decimal::zero(),
-    });
-
-    // Save farm info
-    FARM_INFO.save(deps.storage, &farm_info)?;
+    if farm_info.phases_count - 1 > current_phase_index {
+        return Err(ContractError::PreviousPhaseNotActive {});
+    }
 
     let res = Response::new()
         .add_attribute("method", "add_phase")
         .add_attribute("new_start_time", new_start_time.to_string())
         .add_attribute("new_end_time", new_end_time.to_string())
-        .add_attribute("whitelist", whitelist.to_string());
+        .add_attributes(reward_tokens.iter().map(|reward_token| {
+            (
+                "whitelist",
+                format!("{}:{}", reward_token.info, reward_token.whitelist),
+            )
+        }));
+
+    // Add the new phase after the last phase
+    phases.insert(
+        farm_info.phases_count,
+        PhaseInfo {
+            start_time: new_start_time,
+            end_time: new_end_time,
+            reward_tokens: reward_tokens
+                .into_iter()
+                .map(|reward_token| RewardTokenInfo {
+                    info: reward_token.info,
+                    whitelist: reward_token.whitelist,
+                    reward_balance: Uint128::zero(),
+                    accrued_token_per_share: Decimal256::zero(),
+                    undistributed_reward: Uint128::zero(),
+                    distributed_reward: Uint128::zero(),
+                    claimed_reward: Uint128::zero(),
+                    removed_reward: Uint128::zero(),
+                    emitted_reward: Uint128::zero(),
+                })
+                .collect(),
+            last_reward_time: new_start_time,
+            emission_start_time: None,
+        },
+    );
+    farm_info.phases_count += 1;
+
+    // Save farm info and phases
+    FARM_INFO.save(deps.storage, &farm_info)?;
+    phases.save(deps.storage)?;
 
     Ok(res)
 }
@@ -549,61 +1398,80 @@ pub fn execute_activate_phase(
     let config: Config = CONFIG.load(deps.storage)?;
 
     // Check if the message sender is the owner of the contract
-    if config.farm_owner != info.sender {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Unauthorized: Only owner can active new phase",
-        )));
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
     }
+    // Get current time
+    let current_time = env.block.time.seconds();
+
     // Get farm info
-    let farm_info: FarmInfo = FARM_INFO.load(deps.storage)?;
+    let mut farm_info: FarmInfo = FARM_INFO.load(deps.storage)?;
     // Get current phase index
     let current_phase_index = farm_info.current_phase_index;
 
-    // Not allow active phase when current phase index is equal to farm info length
-    // If sender want to active new phase, they have to add new phase first
-    if farm_info.phases_info.len() == current_phase_index as usize {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Invalid action: Add new phase first",
-        )));
+    // The next phase is activated automatically if it has already started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+    if farm_info.current_phase_index > current_phase_index {
+        FARM_INFO.save(deps.storage, &farm_info)?;
+        phases.save(deps.storage)?;
+
+        return Ok(Response::new().add_attributes([
+            ("method", "activate_phase"),
+            (
+                "activated_phase",
+                &farm_info.current_phase_index.to_string(),
+            ),
+        ]));
     }
 
-    // Get current time
-    let current_time = env.block.time.seconds();
+    // Not allow active phase when current phase is the latest phase
+    // If sender want to active new phase, they have to add new phase first
+    if farm_info.phases_count == current_phase_index + 1 {
+        return Err(ContractError::NoPhaseToActivate {});
+    }
 
     // Not allow activating phase when current time is less than end time of the current phase
     // or greater than start time of the phase to be activated
-    if current_time < farm_info.phases_info[current_phase_index as usize].end_time
-        || current_time > farm_info.phases_info[current_phase_index as usize + 1].start_time
-    {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Current time is not in range of the phase to be activated",
-        )));
+    let earliest = phases.get(deps.storage, current_phase_index)?.end_time;
+    let latest = phases
+        .get(deps.storage, current_phase_index + 1)?
+        .start_time;
+    if current_time < earliest || current_time > latest {
+        return Err(ContractError::InvalidActivationTime {
+            current_time,
+            earliest,
+            latest,
+        });
     }
 
     // Not allow activating phase when reward balance of this phase is zero
-    if farm_info.phases_info[current_phase_index as usize + 1]
-        .reward_balance
-        .is_zero()
+    if phases
+        .get(deps.storage, current_phase_index + 1)?
+        .reward_tokens
+        .iter()
+        .all(|reward_token_info| reward_token_info.reward_balance.is_zero())
     {
-        return Err(ContractError::Std(StdError::generic_err("Empty phase")));
+        return Err(ContractError::EmptyPhase {
+            phase_index: current_phase_index + 1,
+        });
     }
 
-    // Get staked token balance
-    let staked_token_balance = farm_info.staked_token_balance;
-    // Get farm info
-    let mut farm_info: FarmInfo = FARM_INFO.load(deps.storage)?;
+    // Get total reward weight
+    let total_weight = farm_info.total_weight();
 
-    // Get phase info from farm info
-    let phase_info = &mut farm_info.phases_info[current_phase_index as usize];
+    // Get phase info of the current phase
+    let phase_info = phases.get(deps.storage, current_phase_index)?;
 
     // Update reward ratio and time
-    phase_info.update_reward_ratio_and_time(phase_info.end_time, staked_token_balance);
+    phase_info.update_reward_ratio_and_time(phase_info.end_time, total_weight);
 
     // Increase current phase index to activate new phase
     farm_info.current_phase_index += 1;
 
-    // Save farm info
+    // Save farm info and phases
     FARM_INFO.save(deps.storage, &farm_info)?;
+    phases.save(deps.storage)?;
 
     Ok(Response::new().add_attributes([
         ("method", "activate_phase"),
@@ -614,21 +1482,525 @@ pub fn execute_activate_phase(
     ]))
 }
 
+// Get an ended phase of the farm, the current phase is settled until its end time
+fn ended_phase_mut<'a>(
+    storage: &dyn Storage,
+    farm_info: &mut FarmInfo,
+    phases: &'a mut Phases,
+    phase_index: u64,
+    current_time: u64,
+) -> Result<&'a mut PhaseInfo, ContractError> {
+    // Activate the phases which have started
+    farm_info.advance_phases(storage, phases, current_time)?;
+
+    let current_phase_index = farm_info.current_phase_index;
+    let total_weight = farm_info.total_weight();
+
+    // Not allow settling a phase that is not activated
+    if phase_index > current_phase_index {
+        if phase_index >= farm_info.phases_count {
+            return Err(ContractError::PhaseNotFound { phase_index });
+        }
+        return Err(ContractError::PhaseNotActivated { phase_index });
+    }
+
+    // Get phase info
+    let phase_info = phases.get(storage, phase_index)?;
+
+    // Not allow settling before the phase ends
+    if current_time < phase_info.end_time {
+        return Err(ContractError::PhaseNotEnded {
+            phase_index,
+            end_time: phase_info.end_time,
+        });
+    }
+
+    // Settle the current phase until its end time
+    // to count the reward emitted after the last update
+    if phase_index == current_phase_index {
+        phase_info.update_reward_ratio_and_time(current_time, total_weight);
+    }
+
+    Ok(phase_info)
+}
+
+// Transfer the undistributed reward of an ended phase back to the whitelisted address
+pub fn execute_reclaim_undistributed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    phase_index: u64,
+) -> Result<Response, ContractError> {
+    // Get farm info
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    let mut phases = Phases::default();
+    let phase_info = ended_phase_mut(
+        deps.storage,
+        &mut farm_info,
+        &mut phases,
+        phase_index,
+        env.block.time.seconds(),
+    )?;
+
+    // Not allow reclaiming reward tokens which are not whitelisted for the sender
+    if !phase_info
+        .reward_tokens
+        .iter()
+        .any(|reward_token_info| reward_token_info.whitelist == info.sender)
+    {
+        return Err(ContractError::NotWhitelisted {
+            sender: info.sender,
+        });
+    }
+
+    let mut reclaimed_rewards: Vec<RewardTokenAsset> = vec![];
+    for reward_token_info in phase_info
+        .reward_tokens
+        .iter_mut()
+        .filter(|reward_token_info| reward_token_info.whitelist == info.sender)
+    {
+        reclaimed_rewards.push(RewardTokenAsset {
+            info: reward_token_info.info.clone(),
+            amount: reward_token_info.undistributed_reward,
+        });
+        reward_token_info.removed_reward += reward_token_info.undistributed_reward;
+        reward_token_info.undistributed_reward = Uint128::zero();
+    }
+
+    // Check if there is any undistributed reward to reclaim
+    if reclaimed_rewards
+        .iter()
+        .all(|reward| reward.amount.is_zero())
+    {
+        return Err(ContractError::NoUndistributedReward { phase_index });
+    }
+
+    // Save farm info and phases
+    FARM_INFO.save(deps.storage, &farm_info)?;
+    phases.save(deps.storage)?;
+
+    Ok(Response::new()
+        .add_messages(transfer_rewards_msgs(&reclaimed_rewards, &info.sender)?)
+        .add_attribute("method", "reclaim_undistributed")
+        .add_attribute("phase_index", phase_index.to_string())
+        .add_attribute("reclaimed_reward", rewards_to_string(&reclaimed_rewards)))
+}
+
+// Transfer the surplus reward balance of an ended phase back to the whitelisted address,
+// the reward owed to the stakers and the undistributed reward are kept in the farm
+pub fn execute_remove_reward_balance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    phase_index: u64,
+) -> Result<Response, ContractError> {
+    // Get farm info
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    let has_stakers = has_stakers(deps.storage)?;
+    let mut phases = Phases::default();
+    let phase_info = ended_phase_mut(
+        deps.storage,
+        &mut farm_info,
+        &mut phases,
+        phase_index,
+        env.block.time.seconds(),
+    )?;
+
+    // Not allow removing reward tokens which are not whitelisted for the sender
+    if !phase_info
+        .reward_tokens
+        .iter()
+        .any(|reward_token_info| reward_token_info.whitelist == info.sender)
+    {
+        return Err(ContractError::NotWhitelisted {
+            sender: info.sender,
+        });
+    }
+
+    let mut removed_rewards: Vec<RewardTokenAsset> = vec![];
+    for reward_token_info in phase_info
+        .reward_tokens
+        .iter_mut()
+        .filter(|reward_token_info| reward_token_info.whitelist == info.sender)
+    {
+        let surplus_reward = reward_token_info.surplus_reward(has_stakers);
+        removed_rewards.push(RewardTokenAsset {
+            info: reward_token_info.info.clone(),
+            amount: surplus_reward,
+        });
+        reward_token_info.removed_reward += surplus_reward;
+    }
+
+    // Check if there is any surplus reward to remove
+    if removed_rewards.iter().all(|reward| reward.amount.is_zero()) {
+        return Err(ContractError::NoSurplusReward { phase_index });
+    }
+
+    // Save farm info and phases
+    FARM_INFO.save(deps.storage, &farm_info)?;
+    phases.save(deps.storage)?;
+
+    Ok(Response::new()
+        .add_messages(transfer_rewards_msgs(&removed_rewards, &info.sender)?)
+        .add_attribute("method", "remove_reward_balance")
+        .add_attribute("phase_index", phase_index.to_string())
+        .add_attribute("removed_reward", rewards_to_string(&removed_rewards)))
+}
+
 // validate time when creating new farm
 pub fn validate_time_range(env: Env, start_time: u64, end_time: u64) -> Result<(), ContractError> {
     // Not allow start time is greater than end time
     if start_time >= end_time {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Start time is greater than end time",
-        )));
+        return Err(ContractError::InvalidTimeRange {
+            start_time,
+            end_time,
+        });
     }
 
     // Not allow to create a farm when current time is greater than start time
     if env.block.time.seconds() > start_time {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Current time is greater than start time",
-        )));
+        return Err(ContractError::StartTimeInPast {
+            start_time,
+            current_time: env.block.time.seconds(),
+        });
+    }
+
+    Ok(())
+}
+
+// validate reward tokens of a phase
+pub fn validate_reward_tokens(
+    api: &dyn Api,
+    reward_tokens: &[PhaseRewardToken],
+) -> Result<(), ContractError> {
+    // Not allow a phase without reward token
+    if reward_tokens.is_empty() {
+        return Err(ContractError::EmptyRewardTokens {});
+    }
+
+    for (i, reward_token) in reward_tokens.iter().enumerate() {
+        // Validate reward token format
+        match reward_token.info {
+            TokenInfo::NativeToken { ref denom } => {
+                if denom.is_empty() {
+                    return Err(ContractError::EmptyRewardDenom {});
+                }
+            }
+            TokenInfo::Token { ref contract_addr } => {
+                if api.addr_validate(contract_addr.as_ref()).is_err() {
+                    return Err(ContractError::InvalidTokenAddress {
+                        address: contract_addr.to_string(),
+                    });
+                }
+            }
+        }
+
+        // Validate whitelist format
+        if api.addr_validate(reward_token.whitelist.as_ref()).is_err() {
+            return Err(ContractError::InvalidWhitelist {
+                address: reward_token.whitelist.to_string(),
+            });
+        }
+
+        // Not allow duplicated reward tokens
+        if reward_tokens[..i]
+            .iter()
+            .any(|previous| previous.info == reward_token.info)
+        {
+            return Err(ContractError::DuplicatedRewardToken {
+                token: reward_token.info.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// validate phases limit per user, zero limit is not allowed
+pub fn validate_phases_limit_per_user(
+    phases_limit_per_user: Option<Uint128>,
+) -> Result<(), ContractError> {
+    if phases_limit_per_user.map_or(false, |limit| limit.is_zero()) {
+        return Err(ContractError::InvalidPhasesLimitPerUser {});
+    }
+
+    Ok(())
+}
+
+// validate lock tiers, each duration must be unique and not zero
+// and each multiplier must be at least 1
+pub fn validate_lock_tiers(lock_tiers: &[LockTier]) -> Result<(), ContractError> {
+    for (i, lock_tier) in lock_tiers.iter().enumerate() {
+        if lock_tier.duration == 0
+            || lock_tier.multiplier < Decimal::one()
+            || lock_tiers[..i]
+                .iter()
+                .any(|other_lock_tier| other_lock_tier.duration == lock_tier.duration)
+        {
+            return Err(ContractError::InvalidLockTiers {});
+        }
     }
 
     Ok(())
 }
+
+pub fn execute_propose_new_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    expiry: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    // Check if the message sender is the owner of the contract
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Not allow proposing an already expired ownership
+    if let Some(expiry) = expiry {
+        if expiry.is_expired(&env.block) {
+            return Err(ContractError::OwnershipProposalExpired {});
+        }
+    }
+
+    let owner = deps.api.addr_validate(&owner)?;
+
+    // Replace the previous proposal (if any)
+    OWNERSHIP_PROPOSAL.save(
+        deps.storage,
+        &OwnershipProposal {
+            owner: owner.clone(),
+            expiry,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "propose_new_owner"),
+        ("proposed_owner", owner.as_str()),
+        (
+            "expiry",
+            &expiry.map_or("none".to_string(), |expiry| expiry.to_string()),
+        ),
+    ]))
+}
+
+pub fn execute_accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let proposal = OWNERSHIP_PROPOSAL
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoOwnershipProposal {})?;
+
+    // Only the proposed owner can accept the ownership
+    if proposal.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Not allow accepting an expired proposal
+    if let Some(expiry) = proposal.expiry {
+        if expiry.is_expired(&env.block) {
+            return Err(ContractError::OwnershipProposalExpired {});
+        }
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            farm_owner: Some(proposal.owner),
+            ..config
+        },
+    )?;
+    OWNERSHIP_PROPOSAL.remove(deps.storage);
+
+    Ok(Response::new().add_attributes([
+        ("method", "accept_ownership"),
+        ("new_owner", info.sender.as_str()),
+    ]))
+}
+
+pub fn execute_cancel_ownership_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    // Check if the message sender is the owner of the contract
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if OWNERSHIP_PROPOSAL.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoOwnershipProposal {});
+    }
+    OWNERSHIP_PROPOSAL.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("method", "cancel_ownership_proposal"))
+}
+
+pub fn execute_renounce_ownership(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    // Check if the message sender is the owner of the contract
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Nobody could unpause the farm without owner
+    if config.paused {
+        return Err(ContractError::RenounceWhilePaused {});
+    }
+
+    // The farm has no owner anymore, the pending proposal is dropped too
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            farm_owner: None,
+            ..config
+        },
+    )?;
+    OWNERSHIP_PROPOSAL.remove(deps.storage);
+
+    Ok(Response::new().add_attributes([
+        ("method", "renounce_ownership"),
+        ("previous_owner", info.sender.as_str()),
+    ]))
+}
+
+pub fn execute_update_phases_limit_per_user(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_phases_limit_per_user: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    // Check if the message sender is the owner of the contract
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Validate phases limit per user
+    validate_phases_limit_per_user(new_phases_limit_per_user)?;
+
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Stakers above the new limit are not changed, the limit is checked on deposit only
+    farm_info.phases_limit_per_user = new_phases_limit_per_user;
+    FARM_INFO.save(deps.storage, &farm_info)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "update_phases_limit_per_user"),
+        (
+            "new_phases_limit_per_user",
+            &new_phases_limit_per_user.map_or("none".to_string(), |limit| limit.to_string()),
+        ),
+    ]))
+}
+
+pub fn execute_update_lock_tiers(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_lock_tiers: Vec<LockTier>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    // Check if the message sender is the owner of the contract
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Validate lock tiers
+    validate_lock_tiers(&new_lock_tiers)?;
+
+    // The existing locks keep their multiplier and unlock time
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            lock_tiers: new_lock_tiers.clone(),
+            ..config
+        },
+    )?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "update_lock_tiers"),
+        (
+            "new_lock_tiers",
+            &new_lock_tiers
+                .iter()
+                .map(|lock_tier| format!("{}:{}", lock_tier.duration, lock_tier.multiplier))
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+    ]))
+}
+
+// Approve an operator to harvest the reward of the sender to the recipient
+pub fn execute_approve_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    // The harvested reward is sent to the sender by default
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    OPERATORS.save(
+        deps.storage,
+        (info.sender.clone(), operator.clone()),
+        &recipient,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "approve_operator")
+        .add_attribute("staker", info.sender)
+        .add_attribute("operator", operator)
+        .add_attribute("recipient", recipient))
+}
+
+pub fn execute_revoke_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    let key = (info.sender.clone(), operator.clone());
+
+    if !OPERATORS.has(deps.storage, key.clone()) {
+        return Err(ContractError::OperatorNotApproved {
+            operator,
+            staker: info.sender,
+        });
+    }
+
+    OPERATORS.remove(deps.storage, key);
+
+    Ok(Response::new()
+        .add_attribute("method", "revoke_operator")
+        .add_attribute("staker", info.sender)
+        .add_attribute("operator", operator))
+}
+
+pub fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    // Check if the message sender is the owner of the contract
+    if config.farm_owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.paused = paused;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", if paused { "pause" } else { "unpause" }),
+        ("paused", &paused.to_string()),
+    ]))
+}
@@ -1,6 +1,12 @@
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Storage, Uint128, Uint256};
 
-use crate::state::PhaseInfo;
+use crate::state::{
+    BoostCheckpoint, FarmInfo, Lock, PhaseInfo, Phases, RewardTokenInfo, StakerInfo, UnpaidReward,
+};
+
+/// The accrued token per share is scaled by this factor, so the reward per share
+/// keeps 36 decimal places and is not rounded to zero by a large total weight.
+pub const ACCRUED_TOKEN_PER_SHARE_SCALE: u128 = 1_000_000_000_000_000_000;
 
 /// Returns the multiplier over the given _from_ and _to_ range.
 /// The multiplier is zero if the _to_ range is before the _end_.
@@ -16,41 +22,101 @@ pub fn get_multiplier(from: u64, to: u64, end: u64) -> u64 {
     end - from
 }
 
-/// Calculates the reward amount
+/// Calculates the reward emitted over the multiplier seconds of a phase
+/// with the given reward balance and duration.
+pub fn calc_emission(multiplier: u64, reward_balance: Uint128, duration: u64) -> Uint128 {
+    reward_balance.multiply_ratio(multiplier, duration)
+}
+
+/// Calculates the reward emitted per second of a phase with the given reward balance and duration.
+pub fn calc_emission_rate(reward_balance: Uint128, duration: u64) -> Decimal256 {
+    Decimal256::from_ratio(reward_balance, duration)
+}
+
+/// Calculates the increase of the accrued token per share when the reward
+/// is distributed over the total weight.
+pub fn calc_accrued_token_per_share(reward: Uint128, total_weight: Uint128) -> Decimal256 {
+    Decimal256::from_ratio(
+        Uint256::from(reward) * Uint256::from(ACCRUED_TOKEN_PER_SHARE_SCALE),
+        total_weight,
+    )
+}
+
+/// Calculates the reward per second of a unit of weight when the reward
+/// is distributed over the total weight during the given seconds.
+pub fn calc_reward_per_share_rate(
+    reward: Uint128,
+    total_weight: Uint128,
+    seconds: u64,
+) -> Decimal256 {
+    if total_weight.is_zero() || seconds == 0 {
+        return Decimal256::zero();
+    }
+    calc_accrued_token_per_share(reward, total_weight)
+        / Uint256::from(ACCRUED_TOKEN_PER_SHARE_SCALE)
+        / Uint256::from(seconds)
+}
+
+/// Calculates the reward accrued by the weight, rounded down.
+/// It is kept in 256 bits as it can exceed the distributed reward with the reward debt.
+pub fn calc_accrued_reward(weight: Uint128, accrued_token_per_share: Decimal256) -> Uint256 {
+    Uint256::from(weight) * accrued_token_per_share / Uint256::from(ACCRUED_TOKEN_PER_SHARE_SCALE)
+}
+
+/// Calculates the reward amount, only the difference with the reward debt is narrowed
 pub fn calc_reward_amount(
     staked_amount: Uint128,
-    accrued_token_per_share: Decimal,
-    reward_debt: Uint128,
-) -> Uint128 {
-    (staked_amount * accrued_token_per_share)
+    accrued_token_per_share: Decimal256,
+    reward_debt: Uint256,
+) -> StdResult<Uint128> {
+    let reward_amount = calc_accrued_reward(staked_amount, accrued_token_per_share)
         .checked_sub(reward_debt)
-        .unwrap_or(Uint128::zero())
+        .unwrap_or(Uint256::zero());
+    Uint128::try_from(reward_amount).map_err(|source| StdError::ConversionOverflow { source })
 }
 
 impl PhaseInfo {
+    /// Distributes the reward emitted until the current time over the total reward weight.
     pub fn update_reward_ratio_and_time(
         &mut self,
         current_time: u64,
-        staked_token_balance: Uint128,
-    ) -> (Decimal, u64) {
+        total_weight: Uint128,
+    ) -> (Vec<Decimal256>, u64) {
         // If current time is before last reward time, return without updating
         if current_time < self.last_reward_time {
-            return (self.accrued_token_per_share, self.last_reward_time);
+            return (self.accrued_token_per_share(), self.last_reward_time);
         }
 
+        // The reward balance left at the emission start time is emitted until the end time.
+        // The reward of this period is the emission until the current time minus the emission
+        // until the last reward time, so the rounding of each period is not lost.
+        let duration = self.emission_duration();
+        let emitted_time = self.emission_time(self.last_reward_time);
+        let emitting_time = self.emission_time(current_time);
+
         // Check if there is any staked token in the farming pool
-        if staked_token_balance == Uint128::zero() {
-            // No staked token in the farming pool, save last reward time and return
+        if total_weight == Uint128::zero() {
+            // No staked token in the farming pool, the reward emitted in this period
+            // is kept as undistributed reward, save last reward time and return
             self.last_reward_time = current_time;
-            self.accrued_token_per_share = Decimal::zero();
-            (Decimal::zero(), current_time)
+            for reward_token in self.reward_tokens.iter_mut() {
+                reward_token.undistributed_reward +=
+                    reward_token.emission_between(emitted_time, emitting_time, duration);
+                reward_token.accrued_token_per_share = Decimal256::zero();
+            }
+            (
+                vec![Decimal256::zero(); self.reward_tokens.len()],
+                current_time,
+            )
         } else {
-            let multiplier = get_multiplier(self.last_reward_time, current_time, self.end_time);
-            let reward = Uint128::new(multiplier.into()) * self.reward_balance
-                / Uint128::new((self.end_time - self.start_time).into());
+            // Update accrued token per share of each reward token
+            for reward_token in self.reward_tokens.iter_mut() {
+                let reward = reward_token.emission_between(emitted_time, emitting_time, duration);
 
-            let new_accrued_token_per_share = self.accrued_token_per_share
-                + (Decimal::new(reward) / Decimal::new(staked_token_balance));
+                reward_token.distributed_reward += reward;
+                reward_token.accrued_token_per_share +=
+                    calc_accrued_token_per_share(reward, total_weight);
+            }
 
             let new_last_reward_time = if current_time < self.end_time {
                 current_time
@@ -59,27 +125,257 @@ impl PhaseInfo {
             };
 
             self.last_reward_time = new_last_reward_time;
-            self.accrued_token_per_share = new_accrued_token_per_share;
 
-            (new_accrued_token_per_share, new_last_reward_time)
+            (self.accrued_token_per_share(), new_last_reward_time)
         }
     }
+
+    /// Re-derives the emission of each reward token from the remaining reward balance
+    /// and the remaining duration of the phase.
+    /// The phase must be settled until the current time before.
+    pub fn rebase_emission(&mut self, current_time: u64) {
+        for reward_token in self.reward_tokens.iter_mut() {
+            reward_token.emitted_reward =
+                reward_token.distributed_reward + reward_token.undistributed_reward;
+        }
+        self.emission_start_time = Some(current_time.max(self.start_time));
+    }
+
+    /// Returns the duration to emit the reward balance left at the emission start time
+    pub fn emission_duration(&self) -> u64 {
+        self.end_time - self.emission_start_time.unwrap_or(self.start_time)
+    }
+
+    /// Returns the seconds from the emission start time until the given time,
+    /// capped at the end time
+    pub fn emission_time(&self, time: u64) -> u64 {
+        let emission_start_time = self.emission_start_time.unwrap_or(self.start_time);
+        time.clamp(emission_start_time, self.end_time) - emission_start_time
+    }
+
+    /// Returns the reward emitted per second of each reward token
+    /// from the emission start time until the end time
+    pub fn emission_rates(&self) -> Vec<Decimal256> {
+        let duration = self.emission_duration();
+        self.reward_tokens
+            .iter()
+            .map(|reward_token| calc_emission_rate(reward_token.emission_balance(), duration))
+            .collect()
+    }
+
+    /// Returns the accrued token per share of each reward token
+    pub fn accrued_token_per_share(&self) -> Vec<Decimal256> {
+        self.reward_tokens
+            .iter()
+            .map(|reward_token| reward_token.accrued_token_per_share)
+            .collect()
+    }
+}
+
+impl FarmInfo {
+    /// Activates the funded phases which have started at the current time
+    /// and expires the boost weight of the locks unlocked at the current time, in time order.
+    /// Each ended phase is settled until its end time before the next phase is activated,
+    /// the current phase is settled until the unlock time before the boost weight expires.
+    pub fn advance_phases(
+        &mut self,
+        storage: &dyn Storage,
+        phases: &mut Phases,
+        current_time: u64,
+    ) -> StdResult<()> {
+        loop {
+            let mut next_start_time = None;
+            if self.current_phase_index + 1 < self.phases_count {
+                let next_phase_info = phases.get(storage, self.current_phase_index + 1)?;
+                if current_time >= next_phase_info.start_time
+                    && !next_phase_info
+                        .reward_tokens
+                        .iter()
+                        .all(|reward_token_info| reward_token_info.reward_balance.is_zero())
+                {
+                    next_start_time = Some(next_phase_info.start_time);
+                }
+            }
+
+            // The boost expiry before the start time of the next phase is processed first
+            let next_boost_expiry =
+                phases
+                    .next_boost_expiry(storage, current_time)?
+                    .filter(|(unlock_time, _)| {
+                        next_start_time.map_or(true, |start_time| *unlock_time < start_time)
+                    });
+
+            if let Some((unlock_time, boost_expiry)) = next_boost_expiry {
+                let total_weight = self.total_weight();
+                let phase_info = phases.get(storage, self.current_phase_index)?;
+                phase_info.update_reward_ratio_and_time(unlock_time, total_weight);
+
+                let boost_checkpoint = BoostCheckpoint {
+                    phase_index: self.current_phase_index,
+                    accrued_token_per_share: phase_info.accrued_token_per_share(),
+                    locks_count: boost_expiry.locks_count,
+                };
+                phases.expire_boost(unlock_time, boost_checkpoint);
+                self.boost_weight -= boost_expiry.boost_weight;
+            } else if next_start_time.is_some() {
+                let total_weight = self.total_weight();
+                let phase_info = phases.get(storage, self.current_phase_index)?;
+                phase_info.update_reward_ratio_and_time(phase_info.end_time, total_weight);
+
+                self.current_phase_index += 1;
+            } else {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the total reward weight, the staked token balance boosted by the locks
+    pub fn total_weight(&self) -> Uint128 {
+        self.staked_token_balance + self.boost_weight
+    }
+}
+
+impl StakerInfo {
+    /// Returns the reward weight, the staked amount boosted by the locks
+    pub fn weight(&self) -> Uint128 {
+        self.amount + self.boost_weight
+    }
+
+    /// Returns the staked amount which is still locked at the current time
+    pub fn locked_amount(&self, current_time: u64) -> Uint128 {
+        self.locks
+            .iter()
+            .filter(|lock| lock.unlock_time > current_time)
+            .map(|lock| lock.amount)
+            .sum()
+    }
+
+    /// Removes the locks which are unlocked at the current time
+    /// and returns them ordered by their unlock time.
+    /// The boost weight of the locks is released by the caller.
+    pub fn take_expired_locks(&mut self, current_time: u64) -> Vec<Lock> {
+        let (mut expired_locks, locks): (Vec<Lock>, Vec<Lock>) = self
+            .locks
+            .drain(..)
+            .partition(|lock| lock.unlock_time <= current_time);
+        self.locks = locks;
+        expired_locks.sort_by_key(|lock| lock.unlock_time);
+        expired_locks
+    }
+
+    /// Adds the reward accrued in a phase to the unpaid rewards, a zero reward is not kept
+    pub fn add_unpaid_reward(&mut self, phase_index: u64, amounts: Vec<Uint128>) {
+        if amounts.iter().all(|amount| amount.is_zero()) {
+            return;
+        }
+        if let Some(unpaid_reward) = self
+            .unpaid_rewards
+            .iter_mut()
+            .find(|unpaid_reward| unpaid_reward.phase_index == phase_index)
+        {
+            for (unpaid_amount, amount) in unpaid_reward.amounts.iter_mut().zip(amounts) {
+                *unpaid_amount += amount;
+            }
+        } else {
+            self.unpaid_rewards.push(UnpaidReward {
+                phase_index,
+                amounts,
+            });
+        }
+    }
+}
+
+impl Lock {
+    /// Returns the reward weight added to the locked amount by the multiplier
+    pub fn boost_weight(&self) -> Uint128 {
+        self.amount * (self.multiplier - Decimal::one())
+    }
+}
+
+impl RewardTokenInfo {
+    /// Returns the reward balance to be emitted from the emission start time of the phase
+    pub fn emission_balance(&self) -> Uint128 {
+        self.reward_balance.saturating_sub(self.emitted_reward)
+    }
+
+    /// Returns the reward emitted between the given seconds after the emission start time
+    pub fn emission_between(&self, from: u64, to: u64, duration: u64) -> Uint128 {
+        let emission_balance = self.emission_balance();
+        calc_emission(to, emission_balance, duration)
+            - calc_emission(from, emission_balance, duration)
+    }
+
+    /// Returns the reward distributed to the stakers which is not claimed yet.
+    /// Nothing is owed once no staker is left in the farm, not even with an unpaid reward,
+    /// so the rounding dust of the claimed rewards is released.
+    pub fn owed_reward(&self, has_stakers: bool) -> Uint128 {
+        if !has_stakers {
+            return Uint128::zero();
+        }
+        self.distributed_reward.saturating_sub(self.claimed_reward)
+    }
+
+    /// Returns the reward balance left after the owed reward and the undistributed reward.
+    /// It is only the surplus of the phase after the phase has ended,
+    /// because the reward to be emitted until the end time is still included.
+    pub fn surplus_reward(&self, has_stakers: bool) -> Uint128 {
+        self.reward_balance
+            .saturating_sub(self.claimed_reward)
+            .saturating_sub(self.removed_reward)
+            .saturating_sub(self.owed_reward(has_stakers))
+            .saturating_sub(self.undistributed_reward)
+    }
 }
 
 #[cfg(test)]
 mod test_update_reward_ratio_and_time {
-    use cosmwasm_std::{Addr, Decimal, Uint128};
+    use cosmwasm_std::{Addr, Decimal, Decimal256, Uint128, Uint256};
+
+    use super::{calc_accrued_reward, calc_reward_amount, ACCRUED_TOKEN_PER_SHARE_SCALE};
 
-    use crate::state::PhaseInfo;
+    use crate::state::{Lock, PhaseInfo, RewardTokenInfo, StakerInfo, TokenInfo};
+
+    // Accrued token per share of the given percent per staked token
+    fn scaled_percent(percent: u64) -> Decimal256 {
+        Decimal256::percent(percent) * Decimal256::from_ratio(ACCRUED_TOKEN_PER_SHARE_SCALE, 1u128)
+    }
 
     fn get_phase_info() -> PhaseInfo {
         PhaseInfo {
             start_time: 100,
             end_time: 200,
-            whitelist: Addr::unchecked("whitelist"),
-            reward_balance: Uint128::new(1000),
+            reward_tokens: vec![
+                RewardTokenInfo {
+                    info: TokenInfo::NativeToken {
+                        denom: "uaura".to_string(),
+                    },
+                    whitelist: Addr::unchecked("whitelist"),
+                    reward_balance: Uint128::new(1000),
+                    accrued_token_per_share: Decimal256::zero(),
+                    undistributed_reward: Uint128::zero(),
+                    distributed_reward: Uint128::zero(),
+                    claimed_reward: Uint128::zero(),
+                    removed_reward: Uint128::zero(),
+                    emitted_reward: Uint128::zero(),
+                },
+                RewardTokenInfo {
+                    info: TokenInfo::Token {
+                        contract_addr: Addr::unchecked("reward_token"),
+                    },
+                    whitelist: Addr::unchecked("partner_whitelist"),
+                    reward_balance: Uint128::new(500),
+                    accrued_token_per_share: Decimal256::zero(),
+                    undistributed_reward: Uint128::zero(),
+                    distributed_reward: Uint128::zero(),
+                    claimed_reward: Uint128::zero(),
+                    removed_reward: Uint128::zero(),
+                    emitted_reward: Uint128::zero(),
+                },
+            ],
             last_reward_time: 100,
-            accrued_token_per_share: Decimal::zero(),
+            emission_start_time: None,
         }
     }
 
@@ -90,15 +386,27 @@ mod test_update_reward_ratio_and_time {
         // No staked token in the farming pool
         let (new_accrued_token_per_share, new_last_reward_time) =
             phase_info.update_reward_ratio_and_time(150, Uint128::zero());
-        assert_eq!(new_accrued_token_per_share, Decimal::zero());
+        assert_eq!(new_accrued_token_per_share, vec![Decimal256::zero(); 2]);
         // In this case, last reward time should be updated to current time
         // for the contract operation, this case only happens when calling deposit()
         // -> last_reward_time should be updated to current time
         // Withdraw(), Harvest() or QueryPendingReward will not trigger this case.
         assert_eq!(new_last_reward_time, 150);
         // assert phase info is updated
-        assert_eq!(phase_info.accrued_token_per_share, Decimal::zero());
+        assert_eq!(
+            phase_info.accrued_token_per_share(),
+            vec![Decimal256::zero(); 2]
+        );
         assert_eq!(phase_info.last_reward_time, 150);
+        // reward emitted from 100 to 150 is undistributed
+        assert_eq!(
+            phase_info
+                .reward_tokens
+                .iter()
+                .map(|reward_token| reward_token.undistributed_reward)
+                .collect::<Vec<Uint128>>(),
+            vec![Uint128::new(500), Uint128::new(250)]
+        );
     }
 
     #[test]
@@ -109,11 +417,14 @@ mod test_update_reward_ratio_and_time {
         let (new_accrued_token_per_share, new_last_reward_time) =
             phase_info.update_reward_ratio_and_time(50, Uint128::new(100));
 
-        assert_eq!(new_accrued_token_per_share, Decimal::zero());
+        assert_eq!(new_accrued_token_per_share, vec![Decimal256::zero(); 2]);
         assert_eq!(new_last_reward_time, 100);
         // assert phase info is not updated
         assert_eq!(phase_info.last_reward_time, 100);
-        assert_eq!(phase_info.accrued_token_per_share, Decimal::zero());
+        assert_eq!(
+            phase_info.accrued_token_per_share(),
+            vec![Decimal256::zero(); 2]
+        );
     }
 
     #[test]
@@ -124,10 +435,16 @@ mod test_update_reward_ratio_and_time {
         let (new_accrued_token_per_share, new_last_reward_time) =
             phase_info.update_reward_ratio_and_time(150, Uint128::new(100));
 
-        assert_eq!(new_accrued_token_per_share, Decimal::percent(500));
+        assert_eq!(
+            new_accrued_token_per_share,
+            vec![scaled_percent(500), scaled_percent(250)]
+        );
         assert_eq!(new_last_reward_time, 150);
         // assert phase info is updated
-        assert_eq!(phase_info.accrued_token_per_share, Decimal::percent(500));
+        assert_eq!(
+            phase_info.accrued_token_per_share(),
+            vec![scaled_percent(500), scaled_percent(250)]
+        );
         assert_eq!(phase_info.last_reward_time, 150);
     }
 
@@ -138,10 +455,207 @@ mod test_update_reward_ratio_and_time {
         // Staked token in the farming pool and current time is after end time
         let (new_accrued_token_per_share, new_last_reward_time) =
             phase_info.update_reward_ratio_and_time(250, Uint128::new(100));
-        assert_eq!(new_accrued_token_per_share, Decimal::percent(1000));
+        assert_eq!(
+            new_accrued_token_per_share,
+            vec![scaled_percent(1000), scaled_percent(500)]
+        );
         assert_eq!(new_last_reward_time, 200);
         // assert phase info is updated
-        assert_eq!(phase_info.accrued_token_per_share, Decimal::percent(1000));
+        assert_eq!(
+            phase_info.accrued_token_per_share(),
+            vec![scaled_percent(1000), scaled_percent(500)]
+        );
         assert_eq!(phase_info.last_reward_time, 200);
+        // all reward balance is distributed to the stakers
+        assert_eq!(
+            phase_info
+                .reward_tokens
+                .iter()
+                .map(|reward_token| reward_token.distributed_reward)
+                .collect::<Vec<Uint128>>(),
+            vec![Uint128::new(1000), Uint128::new(500)]
+        );
+    }
+
+    #[test]
+    fn test_rebase_emission() {
+        let mut phase_info = get_phase_info();
+
+        // 500 and 250 are emitted in the first half
+        phase_info.update_reward_ratio_and_time(150, Uint128::new(100));
+
+        // Top up 500 of the first reward token and extend the phase to 250
+        phase_info.rebase_emission(150);
+        phase_info.reward_tokens[0].reward_balance += Uint128::new(500);
+        phase_info.end_time = 250;
+
+        // 1000 and 250 are left for the last 100 seconds
+        phase_info.update_reward_ratio_and_time(200, Uint128::new(100));
+        assert_eq!(
+            phase_info.accrued_token_per_share(),
+            vec![scaled_percent(1000), scaled_percent(375)]
+        );
+        phase_info.update_reward_ratio_and_time(300, Uint128::new(100));
+        assert_eq!(
+            phase_info.accrued_token_per_share(),
+            vec![scaled_percent(1500), scaled_percent(500)]
+        );
+        assert_eq!(phase_info.last_reward_time, 250);
+        // all reward balance is distributed to the stakers
+        assert_eq!(
+            phase_info
+                .reward_tokens
+                .iter()
+                .map(|reward_token| reward_token.distributed_reward)
+                .collect::<Vec<Uint128>>(),
+            vec![Uint128::new(1500), Uint128::new(500)]
+        );
+    }
+
+    #[test]
+    fn test_surplus_reward() {
+        let mut phase_info = get_phase_info();
+
+        // No staked token in the first half and 3 staked tokens in the second half
+        phase_info.update_reward_ratio_and_time(150, Uint128::zero());
+        phase_info.update_reward_ratio_and_time(200, Uint128::new(3));
+
+        let reward_token = &mut phase_info.reward_tokens[0];
+        assert_eq!(reward_token.undistributed_reward, Uint128::new(500));
+        assert_eq!(reward_token.distributed_reward, Uint128::new(500));
+        // 3 stakers with 1 staked token each claim their reward
+        reward_token.claimed_reward = Uint128::new(3)
+            * calc_reward_amount(
+                Uint128::one(),
+                reward_token.accrued_token_per_share,
+                Uint256::zero(),
+            )
+            .unwrap();
+        assert_eq!(reward_token.claimed_reward, Uint128::new(498));
+        // The rounding dust is owed while the stakers are still in the farm
+        assert_eq!(reward_token.owed_reward(true), Uint128::new(2));
+        assert_eq!(reward_token.surplus_reward(true), Uint128::zero());
+        // and becomes the surplus after all of them have left
+        assert_eq!(reward_token.owed_reward(false), Uint128::zero());
+        assert_eq!(reward_token.surplus_reward(false), Uint128::new(2));
+
+        // Reclaiming the undistributed reward does not change the surplus
+        reward_token.removed_reward += reward_token.undistributed_reward;
+        reward_token.undistributed_reward = Uint128::zero();
+        assert_eq!(reward_token.surplus_reward(false), Uint128::new(2));
+    }
+
+    #[test]
+    fn test_reward_amount_above_reward_debt() {
+        // The accrued reward of the weight exceeds Uint128 after the reward debt
+        let reward_debt = calc_accrued_reward(Uint128::MAX, scaled_percent(100));
+        assert_eq!(reward_debt, Uint256::from(Uint128::MAX));
+
+        // Only the difference with the reward debt is narrowed
+        assert_eq!(
+            calc_reward_amount(Uint128::MAX, scaled_percent(150), reward_debt).unwrap(),
+            Uint128::MAX / Uint128::new(2)
+        );
+        // A difference above Uint128 is an error
+        assert!(calc_reward_amount(Uint128::MAX, scaled_percent(250), reward_debt).is_err());
+    }
+
+    #[test]
+    fn test_large_total_weight_with_short_intervals() {
+        let mut phase_info = get_phase_info();
+
+        // 3 * 10^27 staked tokens of 18 decimals, the reward is distributed every second
+        let total_weight = Uint128::new(3_000_000_000_000_000_000_000_000_000);
+        for current_time in 101..=200 {
+            phase_info.update_reward_ratio_and_time(current_time, total_weight);
+        }
+
+        // The rounding of each second is emitted in the later seconds
+        assert_eq!(
+            phase_info
+                .reward_tokens
+                .iter()
+                .map(|reward_token| reward_token.distributed_reward)
+                .collect::<Vec<Uint128>>(),
+            vec![Uint128::new(1000), Uint128::new(500)]
+        );
+        // A staker with a third of the staked tokens gets a third of the reward rounded down
+        assert_eq!(
+            phase_info
+                .accrued_token_per_share()
+                .into_iter()
+                .map(|accrued_token_per_share| calc_accrued_reward(
+                    Uint128::new(1_000_000_000_000_000_000_000_000_000),
+                    accrued_token_per_share
+                ))
+                .collect::<Vec<Uint256>>(),
+            vec![Uint256::from(333u128), Uint256::from(166u128)]
+        );
+    }
+
+    #[test]
+    fn test_emission_rates() {
+        let mut phase_info = get_phase_info();
+        assert_eq!(
+            phase_info.emission_rates(),
+            vec![Decimal256::percent(1000), Decimal256::percent(500)]
+        );
+
+        // 500 and 250 are emitted in the first half, then 1000 more of the first reward token
+        // is added and the phase is extended to 300
+        phase_info.update_reward_ratio_and_time(150, Uint128::new(100));
+        phase_info.rebase_emission(150);
+        phase_info.reward_tokens[0].reward_balance += Uint128::new(1000);
+        phase_info.end_time = 300;
+
+        // 1500 and 250 are left for the last 150 seconds
+        assert_eq!(
+            phase_info.emission_rates(),
+            vec![
+                Decimal256::percent(1000),
+                Decimal256::from_ratio(250u128, 150u128)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_take_expired_locks() {
+        let mut staker_info = StakerInfo {
+            amount: Uint128::new(300),
+            reward_debt: vec![],
+            joined_phase: 0,
+            locks: vec![
+                Lock {
+                    amount: Uint128::new(100),
+                    multiplier: Decimal::percent(150),
+                    unlock_time: 150,
+                },
+                Lock {
+                    amount: Uint128::new(100),
+                    multiplier: Decimal::percent(300),
+                    unlock_time: 200,
+                },
+            ],
+            boost_weight: Uint128::new(250),
+            unpaid_rewards: vec![],
+        };
+        assert_eq!(staker_info.weight(), Uint128::new(550));
+        assert_eq!(staker_info.locked_amount(100), Uint128::new(200));
+
+        // Nothing is taken before the first unlock time
+        assert!(staker_info.take_expired_locks(149).is_empty());
+        // The first lock is taken at its unlock time
+        assert_eq!(staker_info.locked_amount(150), Uint128::new(100));
+        let expired_locks = staker_info.take_expired_locks(150);
+        assert_eq!(expired_locks.len(), 1);
+        assert_eq!(expired_locks[0].boost_weight(), Uint128::new(50));
+        assert_eq!(staker_info.locks.len(), 1);
+        // The boost weight is not released by taking the locks
+        assert_eq!(staker_info.weight(), Uint128::new(550));
+        // The second lock is taken after its unlock time
+        let expired_locks = staker_info.take_expired_locks(250);
+        assert_eq!(expired_locks.len(), 1);
+        assert_eq!(expired_locks[0].boost_weight(), Uint128::new(200));
+        assert!(staker_info.locks.is_empty());
     }
 }
@@ -2,6 +2,7 @@ pub mod contract;
 pub mod error;
 pub mod execute;
 pub mod formulas;
+pub mod migrations;
 pub mod msg;
 pub mod query;
 pub mod state;
@@ -0,0 +1,273 @@
+use cosmwasm_std::{Decimal256, Order, StdResult, Storage, Uint128, Uint256};
+use semver::Version;
+
+use crate::formulas::{calc_emission, get_multiplier, ACCRUED_TOKEN_PER_SHARE_SCALE};
+use crate::state::{
+    FarmInfo, PhaseInfo, RewardTokenInfo, StakerInfo, TokenInfo, BOOST_CHECKPOINTS, FARM_INFO,
+    PHASES_INFO, STAKERS_INFO,
+};
+
+/// Runs all state migrations needed by the stored version in order.
+/// Each migration upgrades the state written by the versions before it.
+pub fn migrate_state(storage: &mut dyn Storage, stored_version: &Version) -> StdResult<()> {
+    if *stored_version < Version::new(1, 1, 0) {
+        migrate_from_v1_0_0(storage)?;
+    }
+    if *stored_version < Version::new(1, 2, 0) {
+        migrate_from_v1_1_0(storage)?;
+    }
+    if *stored_version < Version::new(1, 3, 0) {
+        migrate_from_v1_2_0(storage)?;
+    }
+    if *stored_version < Version::new(1, 4, 0) {
+        migrate_from_v1_3_0(storage)?;
+    }
+
+    Ok(())
+}
+
+/// State layout of version 1.0.0 which only supports a single reward token
+/// and a cw20 staked token.
+pub mod v1_0_0 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Addr, Decimal, Uint128};
+    use cw_storage_plus::{Item, Map};
+
+    use crate::state::TokenInfo;
+
+    pub const FARM_INFO: Item<FarmInfo> = Item::new("farm_info");
+
+    pub const STAKERS_INFO: Map<Addr, StakerInfo> = Map::new("stakers_info_response");
+
+    #[cw_serde]
+    pub struct StakerInfo {
+        pub amount: Uint128,
+        pub reward_debt: Vec<Uint128>,
+        pub joined_phase: u64,
+    }
+
+    #[cw_serde]
+    pub struct PhaseInfo {
+        pub start_time: u64,
+        pub end_time: u64,
+        pub whitelist: Addr,
+        pub reward_balance: Uint128,
+        pub last_reward_time: u64,
+        pub accrued_token_per_share: Decimal,
+    }
+
+    #[cw_serde]
+    pub struct FarmInfo {
+        pub staked_token: Addr,
+        pub reward_token: TokenInfo,
+        pub current_phase_index: u64,
+        pub phases_info: Vec<PhaseInfo>,
+        pub phases_limit_per_user: Option<Uint128>,
+        pub staked_token_balance: Uint128,
+    }
+}
+
+/// State layout of version 1.1.0 which keeps all phases inside the farm info.
+/// The accrued token per share of its phases is not scaled yet.
+pub mod v1_1_0 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::Uint128;
+    use cw_storage_plus::Item;
+
+    use crate::state::{PhaseInfo, TokenInfo};
+
+    pub const FARM_INFO: Item<FarmInfo> = Item::new("farm_info");
+
+    #[cw_serde]
+    pub struct FarmInfo {
+        pub staked_token: TokenInfo,
+        pub current_phase_index: u64,
+        pub phases_info: Vec<PhaseInfo>,
+        pub phases_limit_per_user: Option<Uint128>,
+        pub staked_token_balance: Uint128,
+        #[serde(default)]
+        pub boost_weight: Uint128,
+    }
+}
+
+/// State layout of version 1.2.0 which keeps the reward debt of every phase of a staker.
+pub mod v1_2_0 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_storage_plus::Map;
+
+    use crate::state::{Lock, UnpaidReward};
+
+    pub const STAKERS_INFO: Map<Addr, StakerInfo> = Map::new("stakers_info_response");
+
+    #[cw_serde]
+    pub struct StakerInfo {
+        pub amount: Uint128,
+        pub reward_debt: Vec<Vec<Uint128>>,
+        pub joined_phase: u64,
+        #[serde(default)]
+        pub locks: Vec<Lock>,
+        #[serde(default)]
+        pub boost_weight: Uint128,
+        #[serde(default)]
+        pub unpaid_rewards: Vec<UnpaidReward>,
+    }
+}
+
+/// Moves the single reward token of each phase into the reward token list
+/// and the reward debt of each staker into the per reward token debt.
+fn migrate_from_v1_0_0(storage: &mut dyn Storage) -> StdResult<()> {
+    let legacy_farm_info = v1_0_0::FARM_INFO.load(storage)?;
+
+    let farm_info = v1_1_0::FarmInfo {
+        staked_token: TokenInfo::Token {
+            contract_addr: legacy_farm_info.staked_token,
+        },
+        current_phase_index: legacy_farm_info.current_phase_index,
+        phases_info: legacy_farm_info
+            .phases_info
+            .into_iter()
+            .map(|phase_info| {
+                // The claimed reward is not tracked by the legacy layout, all reward emitted
+                // until the last reward time is counted as owed to the stakers
+                let distributed_reward = calc_emission(
+                    get_multiplier(
+                        phase_info.start_time,
+                        phase_info.last_reward_time,
+                        phase_info.end_time,
+                    ),
+                    phase_info.reward_balance,
+                    phase_info.end_time - phase_info.start_time,
+                );
+
+                PhaseInfo {
+                    start_time: phase_info.start_time,
+                    end_time: phase_info.end_time,
+                    reward_tokens: vec![RewardTokenInfo {
+                        info: legacy_farm_info.reward_token.clone(),
+                        whitelist: phase_info.whitelist,
+                        reward_balance: phase_info.reward_balance,
+                        accrued_token_per_share: phase_info.accrued_token_per_share.into(),
+                        undistributed_reward: Uint128::zero(),
+                        distributed_reward,
+                        claimed_reward: Uint128::zero(),
+                        removed_reward: Uint128::zero(),
+                        emitted_reward: Uint128::zero(),
+                    }],
+                    last_reward_time: phase_info.last_reward_time,
+                    emission_start_time: None,
+                }
+            })
+            .collect(),
+        phases_limit_per_user: legacy_farm_info.phases_limit_per_user,
+        staked_token_balance: legacy_farm_info.staked_token_balance,
+        boost_weight: Uint128::zero(),
+    };
+    v1_1_0::FARM_INFO.save(storage, &farm_info)?;
+
+    // Load all legacy stakers first as both layouts share the same namespace
+    let legacy_stakers_info = v1_0_0::STAKERS_INFO
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (staker, legacy_staker_info) in legacy_stakers_info {
+        let staker_info = v1_2_0::StakerInfo {
+            amount: legacy_staker_info.amount,
+            reward_debt: legacy_staker_info
+                .reward_debt
+                .into_iter()
+                .map(|reward_debt: Uint128| vec![reward_debt])
+                .collect(),
+            joined_phase: legacy_staker_info.joined_phase,
+            locks: vec![],
+            boost_weight: Uint128::zero(),
+            unpaid_rewards: vec![],
+        };
+        v1_2_0::STAKERS_INFO.save(storage, staker, &staker_info)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the phases out of the farm info into their own map so that loading
+/// the farm info does not grow with the number of phases.
+fn migrate_from_v1_1_0(storage: &mut dyn Storage) -> StdResult<()> {
+    let legacy_farm_info = v1_1_0::FARM_INFO.load(storage)?;
+
+    let phases_count = legacy_farm_info.phases_info.len() as u64;
+    for (phase_index, phase_info) in legacy_farm_info.phases_info.into_iter().enumerate() {
+        PHASES_INFO.save(storage, phase_index as u64, &phase_info)?;
+    }
+
+    let farm_info = FarmInfo {
+        staked_token: legacy_farm_info.staked_token,
+        current_phase_index: legacy_farm_info.current_phase_index,
+        phases_count,
+        phases_limit_per_user: legacy_farm_info.phases_limit_per_user,
+        staked_token_balance: legacy_farm_info.staked_token_balance,
+        boost_weight: legacy_farm_info.boost_weight,
+    };
+    FARM_INFO.save(storage, &farm_info)?;
+
+    Ok(())
+}
+
+/// Keeps only the reward debt of the joined phase of each staker, the reward debt
+/// of the phases before it is never read.
+fn migrate_from_v1_2_0(storage: &mut dyn Storage) -> StdResult<()> {
+    // Load all legacy stakers first as both layouts share the same namespace
+    let legacy_stakers_info = v1_2_0::STAKERS_INFO
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (staker, legacy_staker_info) in legacy_stakers_info {
+        let staker_info = StakerInfo {
+            amount: legacy_staker_info.amount,
+            reward_debt: legacy_staker_info
+                .reward_debt
+                .get(legacy_staker_info.joined_phase as usize)
+                .map(|reward_debt| reward_debt.iter().copied().map(Uint256::from).collect())
+                .unwrap_or_default(),
+            joined_phase: legacy_staker_info.joined_phase,
+            locks: legacy_staker_info.locks,
+            boost_weight: legacy_staker_info.boost_weight,
+            unpaid_rewards: legacy_staker_info.unpaid_rewards,
+        };
+        STAKERS_INFO.save(storage, staker, &staker_info)?;
+    }
+
+    Ok(())
+}
+
+/// Scales the accrued token per share of all phases and boost checkpoints by
+/// `ACCRUED_TOKEN_PER_SHARE_SCALE`, the reward debt of the stakers is not changed by the scaling.
+fn migrate_from_v1_3_0(storage: &mut dyn Storage) -> StdResult<()> {
+    let phases_info = PHASES_INFO
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (phase_index, mut phase_info) in phases_info {
+        for reward_token in phase_info.reward_tokens.iter_mut() {
+            reward_token.accrued_token_per_share = Decimal256::new(
+                reward_token.accrued_token_per_share.atomics()
+                    * Uint256::from(ACCRUED_TOKEN_PER_SHARE_SCALE),
+            );
+        }
+        PHASES_INFO.save(storage, phase_index, &phase_info)?;
+    }
+
+    let boost_checkpoints = BOOST_CHECKPOINTS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (unlock_time, mut boost_checkpoint) in boost_checkpoints {
+        for accrued_token_per_share in boost_checkpoint.accrued_token_per_share.iter_mut() {
+            *accrued_token_per_share = Decimal256::new(
+                accrued_token_per_share.atomics() * Uint256::from(ACCRUED_TOKEN_PER_SHARE_SCALE),
+            );
+        }
+        BOOST_CHECKPOINTS.save(storage, unlock_time, &boost_checkpoint)?;
+    }
+
+    Ok(())
+}
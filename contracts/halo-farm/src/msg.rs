@@ -1,57 +1,116 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw_utils::Expiration;
 
-use crate::state::{FarmInfo, PendingRewardResponse, StakerInfoResponse, TokenInfo};
+use crate::state::{
+    AllStakersResponse, Config, FarmInfoResponse, LockTier, LocksResponse, OperatorsResponse,
+    OwnershipResponse, PendingRewardResponse, PhaseResponse, PhaseSettlementResponse,
+    PhasesResponse, RewardRateResponse, StakerInfoResponse, StakingCapacityResponse, TokenInfo,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
-    /// Staked Token address
-    pub staked_token: Addr,
-    /// Reward Token address (CW20 or Native)
-    pub reward_token: TokenInfo,
+    /// Staked Token (CW20 or Native)
+    pub staked_token: TokenInfo,
+    /// Reward Tokens (CW20 or Native) with their whitelisted addresses
+    pub reward_tokens: Vec<PhaseRewardToken>,
     /// Start time
     pub start_time: u64,
     /// End time
     pub end_time: u64,
     // The phases limit of staked tokens per user (0 for unlimited)
     pub phases_limit_per_user: Option<Uint128>,
+    // The maximum total staked tokens of the farm (None for unlimited)
+    #[serde(default)]
+    pub max_total_staked: Option<Uint128>,
+    // The lock tiers which can be chosen at deposit time (empty for no locking)
+    #[serde(default)]
+    pub lock_tiers: Vec<LockTier>,
     // Farm Owner
     pub farm_owner: Addr,
-    /// Whitelisted addresses
+}
+
+#[cw_serde]
+pub struct PhaseRewardToken {
+    /// Reward Token (CW20 or Native)
+    pub info: TokenInfo,
+    /// Whitelisted address to add reward balance
     pub whitelist: Addr,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Adding reward balance to a phase by whitelisted address
+    /// Receive cw20 tokens with a hook message
+    Receive(Cw20ReceiveMsg),
+    /// Adding native reward balance to a phase by whitelisted address
     AddRewardBalance {
         /// Reward phase index
         phase_index: u64,
         /// Reward amount
         amount: Uint128,
     },
-    /// Deposit staked tokens and collect reward tokens (if any)
+    /// Deposit native staked tokens sent via funds and collect reward tokens (if any)
     Deposit {
-        amount: Uint128,
+        /// Lock the deposited tokens for the duration of a lock tier (if any)
+        lock_duration: Option<u64>,
     },
-    /// Withdraw staked tokens and collect reward tokens (if any)
+    /// Withdraw staked tokens and collect reward tokens (if any),
+    /// the reward is kept unpaid until the farm is unpaused
     Withdraw {
         amount: Uint128,
+        /// Recipient of the staked tokens and reward tokens, the sender by default
+        recipient: Option<String>,
     },
     // Harvest reward tokens
-    Harvest {},
-    // // Update Phases Limit Per User
-    // UpdatePhasesLimitPerUser {
-    //     new_phases_limit_per_user: Uint128,
-    // },
+    Harvest {
+        /// Recipient of the reward tokens, the sender by default
+        recipient: Option<String>,
+    },
+    /// Harvest reward tokens of a staker by an approved operator,
+    /// the reward tokens are sent to the recipient approved by the staker
+    HarvestFor {
+        staker: String,
+    },
+    /// Approve an operator to harvest the reward tokens of the sender
+    ApproveOperator {
+        operator: String,
+        /// Recipient of the harvested reward tokens, the sender by default
+        recipient: Option<String>,
+    },
+    /// Revoke the approval of an operator
+    RevokeOperator {
+        operator: String,
+    },
+    /// Settle the reward of the ended phases before the given phase index and
+    /// transfer it to the sender, the current phase is not settled.
+    /// The reward is kept unpaid while the farm is paused
+    SettlePhases {
+        up_to: u64,
+    },
+    /// Add the pending reward to the staked amount of the sender
+    /// when the staked token is a reward token, other reward tokens are harvested
+    Compound {},
+    /// Withdraw all staked tokens without collecting reward tokens,
+    /// the pending reward of the sender is forfeited
+    EmergencyWithdraw {},
+    /// Update the maximum staked amount per user by the farm owner, `None` removes the limit.
+    /// Stakers above the new limit keep their stake but can not deposit more
+    UpdatePhasesLimitPerUser {
+        new_phases_limit_per_user: Option<Uint128>,
+    },
+    /// Update the lock tiers by the farm owner, the existing locks are not changed
+    UpdateLockTiers {
+        new_lock_tiers: Vec<LockTier>,
+    },
     // Add a new farming phase
     AddPhase {
         /// New start time
         new_start_time: u64,
         /// New end time
         new_end_time: u64,
-        /// Whitelisted address
-        whitelist: Addr,
+        /// Reward tokens with their whitelisted addresses
+        reward_tokens: Vec<PhaseRewardToken>,
     },
     // Remove inactive farming phase
     RemovePhase {
@@ -60,18 +119,77 @@ pub enum ExecuteMsg {
     },
     // Activate latest farming phase
     ActivatePhase {},
-    // /// Removing reward balance from a phase by whitelisted address
-    // /// Only can be called when the phase is inactive
-    // RemoveRewardBalance {
-    //     /// Reward phase index
-    //     phase_index: u64,
-    // },
+    /// Extend the end time of the current phase by the farm owner,
+    /// the remaining reward balance is emitted until the new end time
+    ExtendPhase {
+        /// New end time
+        new_end_time: u64,
+    },
+    /// Adding native reward balance to the current phase by whitelisted address,
+    /// the remaining reward balance is emitted until the end time
+    TopUpActivePhase {
+        /// Amount of native reward token sent via funds
+        amount: Uint128,
+    },
+    /// Reclaim the reward emitted while there was no staked token
+    /// by the whitelisted address after the phase ends
+    ReclaimUndistributed {
+        /// Reward phase index
+        phase_index: u64,
+    },
+    /// Propose a new owner of the farm, the new owner has to accept the ownership
+    ProposeNewOwner {
+        /// Address of the proposed owner
+        owner: String,
+        /// The proposal can not be accepted after this expiration (if any)
+        expiry: Option<Expiration>,
+    },
+    /// Accept the ownership proposed to the sender
+    AcceptOwnership {},
+    /// Cancel the pending ownership proposal
+    CancelOwnershipProposal {},
+    /// Give up the ownership, the farm will have no owner anymore.
+    /// It is rejected while the farm is paused
+    RenounceOwnership {},
+    /// Pause deposit, harvest, adding reward balance and paying reward on withdrawal
+    Pause {},
+    /// Resume deposit, harvest and adding reward balance
+    Unpause {},
+    /// Removing the surplus reward balance of an ended phase by whitelisted address.
+    /// The reward owed to the stakers is kept in the farm.
+    RemoveRewardBalance {
+        /// Reward phase index
+        phase_index: u64,
+    },
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Deposit staked tokens and collect reward tokens (if any)
+    Deposit {
+        /// Lock the deposited tokens for the duration of a lock tier (if any)
+        lock_duration: Option<u64>,
+    },
+    /// Adding cw20 reward balance to a phase by whitelisted address
+    AddRewardBalance {
+        /// Reward phase index
+        phase_index: u64,
+    },
+    /// Adding cw20 reward balance to the current phase by whitelisted address
+    TopUpActivePhase {},
 }
 
+/// Migrate the farm contract to a new code id, the state is upgraded
+/// by the migrations of the versions after the stored version
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    #[returns(FarmInfo)]
+    #[returns(Config)]
+    Config {},
+    #[returns(FarmInfoResponse)]
     Farm {},
     #[returns(PendingRewardResponse)]
     PendingReward { address: String },
@@ -79,4 +197,51 @@ pub enum QueryMsg {
     TotalStaked {},
     #[returns(StakerInfoResponse)]
     StakerInfo { address: String },
+    #[returns(OwnershipResponse)]
+    Ownership {},
+    /// List stakers with their pending rewards ordered by address,
+    /// a page can have less stakers than the limit to bound the phases to settle
+    #[returns(AllStakersResponse)]
+    AllStakers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Settlement of the reward tokens of a phase, the surplus can be removed
+    /// by the whitelisted address after the phase ends
+    #[returns(PhaseSettlementResponse)]
+    PhaseSettlement { phase_index: u64 },
+    /// A phase with its status, emission rates and remaining time
+    #[returns(PhaseResponse)]
+    Phase { phase_index: u64 },
+    /// List phases with their status, emission rates and remaining time ordered by index
+    #[returns(PhasesResponse)]
+    Phases {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The current phase with its status, emission rates and remaining time
+    #[returns(PhaseResponse)]
+    CurrentPhase {},
+    /// Reward emitted per second by the active phase, the reward per second of a staked unit
+    /// and the projected reward of a deposit amount staked for a duration in the current phase
+    #[returns(RewardRateResponse)]
+    RewardRate {
+        deposit_amount: Option<Uint128>,
+        duration: Option<u64>,
+        /// Lock the deposit amount for the duration of a lock tier (if any)
+        lock_duration: Option<u64>,
+    },
+    /// Total staked tokens with the max total staked and the remaining capacity
+    #[returns(StakingCapacityResponse)]
+    StakingCapacity {},
+    /// Locks of a staker which are not unlocked yet with the reward weight of the staker
+    #[returns(LocksResponse)]
+    Locks { address: String },
+    /// List operators approved by a staker ordered by address
+    #[returns(OperatorsResponse)]
+    Operators {
+        staker: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
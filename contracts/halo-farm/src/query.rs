@@ -1,13 +1,45 @@
 use crate::{
-    execute::claim_all_reward,
+    error::ContractError,
+    execute::{claim_all_reward, MAX_SETTLED_PHASES},
+    formulas::{calc_accrued_token_per_share, calc_reward_amount, calc_reward_per_share_rate},
     state::{
-        FarmInfo, PendingRewardResponse, StakerInfo, StakerInfoResponse, FARM_INFO, STAKERS_INFO,
+        has_stakers, AllStakersResponse, Config, FarmInfo, FarmInfoResponse, LocksResponse,
+        OperatorResponse, OperatorsResponse, OwnershipResponse, PendingRewardResponse, PhaseInfo,
+        PhaseResponse, PhaseSettlementResponse, PhaseStatus, Phases, PhasesResponse,
+        RewardEmission, RewardRateResponse, RewardSettlement, RewardTokenAsset, RewardTokenRate,
+        StakerInfo, StakerInfoResponse, StakerResponse, StakingCapacityResponse, CONFIG, FARM_INFO,
+        OPERATORS, OWNERSHIP_PROPOSAL, STAKERS_INFO,
     },
 };
-use cosmwasm_std::{Addr, Deps, Env, StdResult, Uint128};
+use cosmwasm_std::{Addr, Decimal256, Deps, Env, Order, StdError, StdResult, Uint128, Uint256};
+use cw_storage_plus::Bound;
 
-pub fn query_farm_info(deps: Deps) -> StdResult<FarmInfo> {
-    FARM_INFO.load(deps.storage)
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn query_config(deps: Deps) -> StdResult<Config> {
+    CONFIG.load(deps.storage)
+}
+
+pub fn query_farm_info(deps: Deps, env: Env) -> StdResult<FarmInfoResponse> {
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, env.block.time.seconds())?;
+
+    let phases_info = (0..farm_info.phases_count)
+        .map(|phase_index| Ok(phases.get(deps.storage, phase_index)?.clone()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(FarmInfoResponse {
+        staked_token: farm_info.staked_token,
+        current_phase_index: farm_info.current_phase_index,
+        phases_info,
+        phases_limit_per_user: farm_info.phases_limit_per_user,
+        staked_token_balance: farm_info.staked_token_balance,
+        boost_weight: farm_info.boost_weight,
+    })
 }
 
 pub fn query_pending_reward(
@@ -19,14 +51,24 @@ pub fn query_pending_reward(
     let current_time = env.block.time.seconds();
     // Get farm info
     let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
     // Check if staker has staked in the farm contract
     if STAKERS_INFO
         .may_load(deps.storage, Addr::unchecked(address.clone()))?
         .is_none()
     {
         return Ok(PendingRewardResponse {
-            info: farm_info.reward_token,
-            amount: Uint128::zero(),
+            rewards: phases
+                .get(deps.storage, farm_info.current_phase_index)?
+                .reward_tokens
+                .iter()
+                .map(|reward_token_info| RewardTokenAsset {
+                    info: reward_token_info.info.clone(),
+                    amount: Uint128::zero(),
+                })
+                .collect(),
             time_query: current_time,
         });
     }
@@ -35,11 +77,17 @@ pub fn query_pending_reward(
         .load(deps.storage, Addr::unchecked(address))
         .unwrap();
 
-    let reward_amount = claim_all_reward(&mut farm_info, &mut staker_info, current_time);
+    let rewards = claim_all_reward(
+        deps.storage,
+        &mut farm_info,
+        &mut phases,
+        &mut staker_info,
+        current_time,
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
 
     Ok(PendingRewardResponse {
-        info: farm_info.reward_token,
-        amount: reward_amount,
+        rewards,
         time_query: current_time,
     })
 }
@@ -48,6 +96,19 @@ pub fn query_total_lp_token_staked(deps: Deps) -> StdResult<Uint128> {
     Ok(FARM_INFO.load(deps.storage)?.staked_token_balance)
 }
 
+pub fn query_staking_capacity(deps: Deps) -> StdResult<StakingCapacityResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let total_staked = FARM_INFO.load(deps.storage)?.staked_token_balance;
+
+    Ok(StakingCapacityResponse {
+        total_staked,
+        max_total_staked: config.max_total_staked,
+        remaining_capacity: config
+            .max_total_staked
+            .map(|max_total_staked| max_total_staked.saturating_sub(total_staked)),
+    })
+}
+
 pub fn query_staker_info(deps: Deps, address: String) -> StdResult<StakerInfoResponse> {
     // Get staker info
     let staker_info = STAKERS_INFO
@@ -56,9 +117,443 @@ pub fn query_staker_info(deps: Deps, address: String) -> StdResult<StakerInfoRes
             amount: Uint128::zero(),
             reward_debt: vec![],
             joined_phase: 0u64,
+            locks: vec![],
+            boost_weight: Uint128::zero(),
+            unpaid_rewards: vec![],
         });
     Ok(StakerInfoResponse {
         amount: staker_info.amount,
         joined_phase: staker_info.joined_phase,
     })
 }
+
+pub fn query_ownership(deps: Deps) -> StdResult<OwnershipResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal = OWNERSHIP_PROPOSAL.may_load(deps.storage)?;
+
+    Ok(OwnershipResponse {
+        owner: config.farm_owner,
+        pending_owner: proposal.as_ref().map(|proposal| proposal.owner.clone()),
+        pending_expiry: proposal.and_then(|proposal| proposal.expiry),
+    })
+}
+
+pub fn query_all_stakers(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllStakersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|staker| Bound::exclusive(Addr::unchecked(staker)));
+
+    // Get current time
+    let current_time = env.block.time.seconds();
+    // Get farm info, the reward ratio of the current phase is updated once by the first staker
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+
+    // The page ends before the phases settled for its stakers exceed the maximum
+    // settled by a message, so it can have less stakers than the limit
+    let mut settled_phases = 0u64;
+    let mut stakers: Vec<StakerResponse> = vec![];
+    for item in STAKERS_INFO
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+    {
+        let (address, mut staker_info) = item?;
+        settled_phases += farm_info.current_phase_index - staker_info.joined_phase;
+        if settled_phases > MAX_SETTLED_PHASES && !stakers.is_empty() {
+            break;
+        }
+
+        let amount = staker_info.amount;
+        let joined_phase = staker_info.joined_phase;
+        let pending_rewards = claim_all_reward(
+            deps.storage,
+            &mut farm_info,
+            &mut phases,
+            &mut staker_info,
+            current_time,
+        )
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+        stakers.push(StakerResponse {
+            address,
+            amount,
+            joined_phase,
+            pending_rewards,
+        });
+    }
+
+    Ok(AllStakersResponse {
+        stakers,
+        time_query: current_time,
+    })
+}
+
+pub fn query_phase_settlement(
+    deps: Deps,
+    env: Env,
+    phase_index: u64,
+) -> StdResult<PhaseSettlementResponse> {
+    // Get current time
+    let current_time = env.block.time.seconds();
+    // Get farm info
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+    let current_phase_index = farm_info.current_phase_index;
+    let has_stakers = has_stakers(deps.storage)?;
+    let total_weight = farm_info.total_weight();
+
+    if phase_index >= farm_info.phases_count {
+        return Err(StdError::generic_err(
+            ContractError::PhaseNotFound { phase_index }.to_string(),
+        ));
+    }
+    let phase_info = phases.get(deps.storage, phase_index)?;
+
+    // Settle the current phase until the current time
+    if phase_index == current_phase_index {
+        phase_info.update_reward_ratio_and_time(current_time, total_weight);
+    }
+
+    let ended = phase_index <= current_phase_index && current_time >= phase_info.end_time;
+
+    let rewards = phase_info
+        .reward_tokens
+        .iter()
+        .map(|reward_token_info| RewardSettlement {
+            info: reward_token_info.info.clone(),
+            whitelist: reward_token_info.whitelist.clone(),
+            reward_balance: reward_token_info.reward_balance,
+            distributed_reward: reward_token_info.distributed_reward,
+            claimed_reward: reward_token_info.claimed_reward,
+            owed_reward: reward_token_info.owed_reward(has_stakers),
+            undistributed_reward: reward_token_info.undistributed_reward,
+            removed_reward: reward_token_info.removed_reward,
+            surplus_reward: if ended {
+                reward_token_info.surplus_reward(has_stakers)
+            } else {
+                Uint128::zero()
+            },
+        })
+        .collect();
+
+    Ok(PhaseSettlementResponse {
+        phase_index,
+        ended,
+        rewards,
+        time_query: current_time,
+    })
+}
+
+pub fn query_phase(deps: Deps, env: Env, phase_index: u64) -> StdResult<PhaseResponse> {
+    // Get current time
+    let current_time = env.block.time.seconds();
+    // Get farm info
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+
+    if phase_index >= farm_info.phases_count {
+        return Err(StdError::generic_err(
+            ContractError::PhaseNotFound { phase_index }.to_string(),
+        ));
+    }
+
+    phase_response(deps, &farm_info, &mut phases, phase_index, current_time)
+}
+
+pub fn query_current_phase(deps: Deps, env: Env) -> StdResult<PhaseResponse> {
+    // Get current time
+    let current_time = env.block.time.seconds();
+    // Get farm info
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+
+    let current_phase_index = farm_info.current_phase_index;
+    phase_response(
+        deps,
+        &farm_info,
+        &mut phases,
+        current_phase_index,
+        current_time,
+    )
+}
+
+pub fn query_phases(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PhasesResponse> {
+    // Get current time
+    let current_time = env.block.time.seconds();
+    // Get farm info
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as u64;
+    let start = start_after.map_or(0, |phase_index| phase_index + 1);
+    let end = start.saturating_add(limit).min(farm_info.phases_count);
+
+    let phases_response = (start..end)
+        .map(|phase_index| phase_response(deps, &farm_info, &mut phases, phase_index, current_time))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PhasesResponse {
+        phases: phases_response,
+        time_query: current_time,
+    })
+}
+
+// Build the response of a phase, the current phase is settled until the current time
+fn phase_response(
+    deps: Deps,
+    farm_info: &FarmInfo,
+    phases: &mut Phases,
+    phase_index: u64,
+    current_time: u64,
+) -> StdResult<PhaseResponse> {
+    let current_phase_index = farm_info.current_phase_index;
+    let total_weight = farm_info.total_weight();
+    let phase_info = phases.get(deps.storage, phase_index)?;
+
+    if phase_index == current_phase_index {
+        phase_info.update_reward_ratio_and_time(current_time, total_weight);
+    }
+
+    let status = phase_status(phase_info, phase_index, current_phase_index, current_time);
+    // The reward is emitted from the start time until the end time
+    let time_remaining = phase_info
+        .end_time
+        .saturating_sub(current_time.max(phase_info.start_time));
+    let emission_rates = phase_info
+        .reward_tokens
+        .iter()
+        .zip(phase_info.emission_rates())
+        .map(|(reward_token_info, emission_rate)| RewardEmission {
+            info: reward_token_info.info.clone(),
+            emission_rate,
+        })
+        .collect();
+
+    Ok(PhaseResponse {
+        phase_index,
+        status,
+        phase_info: phase_info.clone(),
+        emission_rates,
+        time_remaining,
+    })
+}
+
+pub fn query_reward_rate(
+    deps: Deps,
+    env: Env,
+    deposit_amount: Option<Uint128>,
+    duration: Option<u64>,
+    lock_duration: Option<u64>,
+) -> StdResult<RewardRateResponse> {
+    // Get current time
+    let current_time = env.block.time.seconds();
+    // The lock duration must be one of the lock tiers
+    let lock_tier = match lock_duration {
+        Some(duration) => Some(
+            CONFIG
+                .load(deps.storage)?
+                .lock_tiers
+                .into_iter()
+                .find(|lock_tier| lock_tier.duration == duration)
+                .ok_or_else(|| {
+                    StdError::generic_err(
+                        ContractError::InvalidLockDuration { duration }.to_string(),
+                    )
+                })?,
+        ),
+        None => None,
+    };
+    // Get farm info
+    let mut farm_info = FARM_INFO.load(deps.storage)?;
+    // Activate the phases which have started
+    let mut phases = Phases::default();
+    farm_info.advance_phases(deps.storage, &mut phases, current_time)?;
+
+    let current_phase_index = farm_info.current_phase_index;
+    let total_weight = farm_info.total_weight();
+    let phase_info = phases.get(deps.storage, current_phase_index)?;
+    let status = phase_status(
+        phase_info,
+        current_phase_index,
+        current_phase_index,
+        current_time,
+    );
+
+    // The deposit is boosted by the lock until the unlock time
+    let deposit_amount = deposit_amount.unwrap_or_default();
+    let boosted_amount = match &lock_tier {
+        Some(lock_tier) => deposit_amount * lock_tier.multiplier,
+        None => deposit_amount,
+    };
+    let deposit_time = current_time.saturating_add(duration.unwrap_or_default());
+    let unlock_time = match &lock_tier {
+        Some(lock_tier) => deposit_time.min(current_time.saturating_add(lock_tier.duration)),
+        None => deposit_time,
+    };
+    // The deposit only earns the reward emitted by the current phase
+    let emission_duration = phase_info.emission_duration();
+    let emitted_time = phase_info.emission_time(current_time);
+    let unlocked_time = phase_info.emission_time(unlock_time);
+    let emitting_time = phase_info.emission_time(deposit_time);
+
+    let rewards = phase_info
+        .reward_tokens
+        .iter()
+        .zip(phase_info.emission_rates())
+        .map(|(reward_token_info, emission_rate)| {
+            // The reward left to be emitted until the end time is distributed
+            // over the current total weight
+            let (emission_rate, reward_per_share_rate) = match status {
+                PhaseStatus::Active => (
+                    emission_rate,
+                    calc_reward_per_share_rate(
+                        reward_token_info.emission_between(
+                            emitted_time,
+                            emission_duration,
+                            emission_duration,
+                        ),
+                        total_weight,
+                        emission_duration - emitted_time,
+                    ),
+                ),
+                _ => (Decimal256::zero(), Decimal256::zero()),
+            };
+
+            let projected_reward = if deposit_amount.is_zero() {
+                Uint128::zero()
+            } else {
+                let locked_reward = reward_token_info.emission_between(
+                    emitted_time,
+                    unlocked_time,
+                    emission_duration,
+                );
+                let unlocked_reward = reward_token_info.emission_between(
+                    unlocked_time,
+                    emitting_time,
+                    emission_duration,
+                );
+                // The deposit has no reward debt, its boost ends at the unlock time
+                calc_reward_amount(
+                    boosted_amount,
+                    calc_accrued_token_per_share(locked_reward, total_weight + boosted_amount),
+                    Uint256::zero(),
+                )? + calc_reward_amount(
+                    deposit_amount,
+                    calc_accrued_token_per_share(unlocked_reward, total_weight + deposit_amount),
+                    Uint256::zero(),
+                )?
+            };
+
+            Ok(RewardTokenRate {
+                info: reward_token_info.info.clone(),
+                emission_rate,
+                reward_per_share_rate,
+                projected_reward,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(RewardRateResponse {
+        phase_index: current_phase_index,
+        status,
+        total_weight,
+        rewards,
+        time_query: current_time,
+    })
+}
+
+// Get the status of a phase at the current time
+fn phase_status(
+    phase_info: &PhaseInfo,
+    phase_index: u64,
+    current_phase_index: u64,
+    current_time: u64,
+) -> PhaseStatus {
+    if phase_index <= current_phase_index && current_time >= phase_info.end_time {
+        PhaseStatus::Ended
+    } else if phase_info
+        .reward_tokens
+        .iter()
+        .all(|reward_token_info| reward_token_info.reward_balance.is_zero())
+    {
+        PhaseStatus::Pending
+    } else if phase_index == current_phase_index && current_time >= phase_info.start_time {
+        PhaseStatus::Active
+    } else {
+        PhaseStatus::Funded
+    }
+}
+
+pub fn query_operators(
+    deps: Deps,
+    staker: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OperatorsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|operator| Bound::exclusive(Addr::unchecked(operator)));
+
+    let operators = OPERATORS
+        .prefix(Addr::unchecked(staker))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (operator, recipient) = item?;
+            Ok(OperatorResponse {
+                operator,
+                recipient,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(OperatorsResponse { operators })
+}
+
+pub fn query_locks(deps: Deps, env: Env, address: String) -> StdResult<LocksResponse> {
+    // Get current time
+    let current_time = env.block.time.seconds();
+
+    let (locks, locked_amount, weight) =
+        match STAKERS_INFO.may_load(deps.storage, Addr::unchecked(address))? {
+            Some(mut staker_info) => {
+                // The boost weight of the expired locks is not counted after their unlock time
+                let expired_boost_weight: Uint128 = staker_info
+                    .take_expired_locks(current_time)
+                    .iter()
+                    .map(|lock| lock.boost_weight())
+                    .sum();
+                (
+                    staker_info.locks.clone(),
+                    staker_info.locked_amount(current_time),
+                    staker_info.weight() - expired_boost_weight,
+                )
+            }
+            None => (vec![], Uint128::zero(), Uint128::zero()),
+        };
+
+    Ok(LocksResponse {
+        locks,
+        locked_amount,
+        weight,
+        time_query: current_time,
+    })
+}
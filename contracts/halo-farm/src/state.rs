@@ -1,26 +1,139 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    Addr, Decimal, Decimal256, Order, StdError, StdResult, Storage, Uint128, Uint256,
+};
+use cw_storage_plus::{Bound, Item, Map};
+use cw_utils::Expiration;
+use std::collections::btree_map::{BTreeMap, Entry};
 use std::fmt;
 
 #[cw_serde]
 pub struct Config {
-    pub farm_owner: Addr,
+    pub farm_owner: Option<Addr>, // None after the owner renounced the ownership
+    #[serde(default)]
+    pub paused: bool, // Deposit, harvest and adding reward balance are blocked while paused
+    #[serde(default)]
+    pub max_total_staked: Option<Uint128>, // Deposits above this total staked amount are rejected
+    #[serde(default)]
+    pub lock_tiers: Vec<LockTier>, // Lock tiers which can be chosen at deposit time
+}
+
+#[cw_serde]
+pub struct LockTier {
+    pub duration: u64,       // Lock duration in seconds
+    pub multiplier: Decimal, // Reward weight multiplier of the locked amount, at least 1
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
-/// Stores info of a farm.
+/// Stores the new owner proposed by the current owner.
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+#[cw_serde]
+pub struct OwnershipProposal {
+    pub owner: Addr,
+    pub expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct OwnershipResponse {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+/// Stores info of a farm, the phases are stored separately in `PHASES_INFO`.
 pub const FARM_INFO: Item<FarmInfo> = Item::new("farm_info");
 
+/// Mapping from phase index to phase info.
+pub const PHASES_INFO: Map<u64, PhaseInfo> = Map::new("phases_info");
+
 /// Mappping from staker address to staker balance.
 pub const STAKERS_INFO: Map<Addr, StakerInfo> = Map::new("stakers_info_response");
 
+/// Returns true if any staker is left in the farm, including the ones with only unpaid rewards.
+pub fn has_stakers(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(STAKERS_INFO
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?
+        .is_some())
+}
+
+/// Mapping from unlock time to the boost weight of the locks expiring at that time.
+pub const BOOST_EXPIRIES: Map<u64, BoostExpiry> = Map::new("boost_expiries");
+
+/// Mapping from unlock time to the accrued token per share when the locks expired,
+/// kept until the boosted reward of all the expired locks is claimed.
+pub const BOOST_CHECKPOINTS: Map<u64, BoostCheckpoint> = Map::new("boost_checkpoints");
+
+/// Mapping from (staker, operator) to the recipient of the reward harvested by the operator.
+pub const OPERATORS: Map<(Addr, Addr), Addr> = Map::new("operators");
+
+#[cw_serde]
+pub struct OperatorResponse {
+    pub operator: Addr,
+    pub recipient: Addr, // The reward harvested by the operator is sent to this address
+}
+
+#[cw_serde]
+pub struct OperatorsResponse {
+    pub operators: Vec<OperatorResponse>,
+}
+
+#[cw_serde]
+pub struct StakingCapacityResponse {
+    pub total_staked: Uint128,
+    pub max_total_staked: Option<Uint128>,
+    pub remaining_capacity: Option<Uint128>, // None if there is no max total staked
+}
+
 #[cw_serde]
 pub struct StakerInfo {
     pub amount: Uint128,           // How many staked tokens the user has provided.
-    pub reward_debt: Vec<Uint128>, // Store reward debt in multiple phases.
+    pub reward_debt: Vec<Uint256>, // Store reward debt of each reward token in the joined phase.
     pub joined_phase: u64,
+    #[serde(default)]
+    pub locks: Vec<Lock>, // Locked parts of the staked amount
+    #[serde(default)]
+    pub boost_weight: Uint128, // Reward weight added by the multipliers of the locks
+    #[serde(default)]
+    pub unpaid_rewards: Vec<UnpaidReward>, // Reward accrued by a withdrawal while the farm is paused
+}
+
+// Reward of a phase accrued by a staker which is not paid yet
+#[cw_serde]
+pub struct UnpaidReward {
+    pub phase_index: u64,
+    pub amounts: Vec<Uint128>, // Amount of each reward token of the phase
+}
+
+#[cw_serde]
+pub struct Lock {
+    pub amount: Uint128,
+    pub multiplier: Decimal,
+    pub unlock_time: u64, // The locked amount can be withdrawn from this time
+}
+
+#[cw_serde]
+pub struct BoostExpiry {
+    pub boost_weight: Uint128, // Total boost weight of the locks expiring at the same time
+    pub locks_count: u64,
+}
+
+#[cw_serde]
+pub struct BoostCheckpoint {
+    pub phase_index: u64,                         // Current phase at the unlock time
+    pub accrued_token_per_share: Vec<Decimal256>, // Of the current phase at the unlock time
+    pub locks_count: u64,                         // Expired locks not released by their stakers yet
+}
+
+#[cw_serde]
+pub struct LocksResponse {
+    pub locks: Vec<Lock>, // Locks which are not unlocked at the query time
+    pub locked_amount: Uint128,
+    pub weight: Uint128, // Reward weight of the staker
+    pub time_query: u64,
 }
 
 #[cw_serde]
@@ -29,13 +142,110 @@ pub struct StakerInfoResponse {
     pub joined_phase: u64,
 }
 
+#[cw_serde]
+pub struct StakerResponse {
+    pub address: Addr,
+    pub amount: Uint128,
+    pub joined_phase: u64,
+    pub pending_rewards: Vec<RewardTokenAsset>,
+}
+
+#[cw_serde]
+pub struct AllStakersResponse {
+    pub stakers: Vec<StakerResponse>,
+    pub time_query: u64,
+}
+
 #[cw_serde]
 pub struct PendingRewardResponse {
+    pub rewards: Vec<RewardTokenAsset>,
+    pub time_query: u64,
+}
+
+#[cw_serde]
+pub struct PhaseSettlementResponse {
+    pub phase_index: u64,
+    pub ended: bool,
+    pub rewards: Vec<RewardSettlement>,
+    pub time_query: u64,
+}
+
+// Status of a phase at the query time
+#[cw_serde]
+pub enum PhaseStatus {
+    Pending, // No reward balance has been added yet
+    Funded,  // Funded but not started or not activated yet
+    Active,  // The current phase, emitting the reward until the end time
+    Ended,
+}
+
+#[cw_serde]
+pub struct PhaseResponse {
+    pub phase_index: u64,
+    pub status: PhaseStatus,
+    pub phase_info: PhaseInfo,
+    pub emission_rates: Vec<RewardEmission>,
+    pub time_remaining: u64, // Seconds left to emit the reward, zero after the phase ends
+}
+
+#[cw_serde]
+pub struct PhasesResponse {
+    pub phases: Vec<PhaseResponse>,
+    pub time_query: u64,
+}
+
+// Emission of a reward token of a phase
+#[cw_serde]
+pub struct RewardEmission {
     pub info: TokenInfo,
-    pub amount: Uint128,
+    pub emission_rate: Decimal256, // Reward emitted per second until the end time
+}
+
+#[cw_serde]
+pub struct RewardRateResponse {
+    pub phase_index: u64,
+    pub status: PhaseStatus,
+    pub total_weight: Uint128,
+    pub rewards: Vec<RewardTokenRate>,
     pub time_query: u64,
 }
 
+// Reward rate of a reward token of the current phase
+#[cw_serde]
+pub struct RewardTokenRate {
+    pub info: TokenInfo,
+    pub emission_rate: Decimal256, // Reward emitted per second, zero if the phase is not active
+    pub reward_per_share_rate: Decimal256, // Reward per second of a staked unit of weight
+    pub projected_reward: Uint128, // Reward of the deposit amount over the duration
+}
+
+// Settlement of a reward token of a phase
+#[cw_serde]
+pub struct RewardSettlement {
+    pub info: TokenInfo,
+    pub whitelist: Addr,
+    pub reward_balance: Uint128,
+    pub distributed_reward: Uint128, // Reward emitted to the stakers so far
+    pub claimed_reward: Uint128,
+    pub owed_reward: Uint128, // Reward distributed to the stakers but not claimed yet
+    pub undistributed_reward: Uint128,
+    pub removed_reward: Uint128,
+    pub surplus_reward: Uint128, // Zero until the phase ends
+}
+
+// RewardTokenAsset is an amount of a reward token
+#[cw_serde]
+pub struct RewardTokenAsset {
+    pub info: TokenInfo,
+    pub amount: Uint128,
+}
+
+impl fmt::Display for RewardTokenAsset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.info)
+    }
+}
+
 // TokenInfo is an enum that can be either a Token or a NativeToken
 #[cw_serde]
 pub enum TokenInfo {
@@ -52,23 +262,214 @@ impl fmt::Display for TokenInfo {
     }
 }
 
+// Reward token info of a phase
+#[cw_serde]
+pub struct RewardTokenInfo {
+    pub info: TokenInfo,
+    pub whitelist: Addr, // Whitelisted address to add reward balance
+    pub reward_balance: Uint128,
+    pub accrued_token_per_share: Decimal256, // Scaled by ACCRUED_TOKEN_PER_SHARE_SCALE
+    #[serde(default)]
+    pub undistributed_reward: Uint128, // Reward emitted while there was no staked token
+    #[serde(default)]
+    pub distributed_reward: Uint128, // Reward emitted while there was staked token
+    #[serde(default)]
+    pub claimed_reward: Uint128, // Reward paid out to the stakers
+    #[serde(default)]
+    pub removed_reward: Uint128, // Reward transferred back to the whitelisted address
+    #[serde(default)]
+    pub emitted_reward: Uint128, // Reward emitted before the emission start time of the phase
+}
+
 // We define a custom struct for each query response
 #[cw_serde]
 pub struct PhaseInfo {
     pub start_time: u64,
     pub end_time: u64,
-    pub whitelist: Addr, // Whitelisted address to add reward balance
-    pub reward_balance: Uint128,
+    pub reward_tokens: Vec<RewardTokenInfo>,
     pub last_reward_time: u64,
-    pub accrued_token_per_share: Decimal,
+    #[serde(default)]
+    pub emission_start_time: Option<u64>, // Set when the emission is re-derived, start time if not set
 }
 
 #[cw_serde]
 pub struct FarmInfo {
-    pub staked_token: Addr,
-    pub reward_token: TokenInfo,
+    pub staked_token: TokenInfo,
     pub current_phase_index: u64,
-    pub phases_info: Vec<PhaseInfo>,
+    pub phases_count: u64, // Number of phases stored in PHASES_INFO
     pub phases_limit_per_user: Option<Uint128>,
     pub staked_token_balance: Uint128, // Total staked token balance in the farm contract
+    pub boost_weight: Uint128,         // Total reward weight added by the multipliers of the locks
+}
+
+#[cw_serde]
+pub struct FarmInfoResponse {
+    pub staked_token: TokenInfo,
+    pub current_phase_index: u64,
+    pub phases_info: Vec<PhaseInfo>,
+    pub phases_limit_per_user: Option<Uint128>,
+    pub staked_token_balance: Uint128,
+    pub boost_weight: Uint128,
+}
+
+/// Phases loaded from `PHASES_INFO` while handling a message, together with
+/// the boost expiries and checkpoints changed by the message.
+/// The loaded phases are updated in memory and written back together by `save`.
+#[derive(Default)]
+pub struct Phases {
+    loaded: BTreeMap<u64, PhaseInfo>,
+    boost_expiries: BTreeMap<u64, Option<BoostExpiry>>, // None when removed
+    boost_checkpoints: BTreeMap<u64, Option<BoostCheckpoint>>, // None when removed
+    expired_until: Option<u64>, // Unlock time of the last boost expiry processed
+}
+
+impl Phases {
+    /// Returns the phase, it is loaded from the storage on the first access
+    pub fn get(&mut self, storage: &dyn Storage, phase_index: u64) -> StdResult<&mut PhaseInfo> {
+        match self.loaded.entry(phase_index) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => Ok(entry.insert(PHASES_INFO.load(storage, phase_index)?)),
+        }
+    }
+
+    /// Adds a new phase to be saved
+    pub fn insert(&mut self, phase_index: u64, phase_info: PhaseInfo) {
+        self.loaded.insert(phase_index, phase_info);
+    }
+
+    /// Removes a phase from the storage
+    pub fn remove(&mut self, storage: &mut dyn Storage, phase_index: u64) {
+        self.loaded.remove(&phase_index);
+        PHASES_INFO.remove(storage, phase_index);
+    }
+
+    /// Returns the first boost expiry not processed yet with an unlock time
+    /// not after the current time
+    pub fn next_boost_expiry(
+        &self,
+        storage: &dyn Storage,
+        current_time: u64,
+    ) -> StdResult<Option<(u64, BoostExpiry)>> {
+        BOOST_EXPIRIES
+            .range(
+                storage,
+                self.expired_until.map(Bound::exclusive),
+                Some(Bound::inclusive(current_time)),
+                Order::Ascending,
+            )
+            .next()
+            .transpose()
+    }
+
+    /// Replaces the boost expiry of the unlock time by its checkpoint
+    pub fn expire_boost(&mut self, unlock_time: u64, boost_checkpoint: BoostCheckpoint) {
+        self.boost_expiries.insert(unlock_time, None);
+        self.boost_checkpoints
+            .insert(unlock_time, Some(boost_checkpoint));
+        self.expired_until = Some(unlock_time);
+    }
+
+    /// Adds the boost weight of a new lock to the boost expiry of its unlock time
+    pub fn add_boost_expiry(
+        &mut self,
+        storage: &dyn Storage,
+        unlock_time: u64,
+        boost_weight: Uint128,
+    ) -> StdResult<()> {
+        let boost_expiry = self.boost_expiry(storage, unlock_time)?;
+        let boost_expiry = boost_expiry.get_or_insert(BoostExpiry {
+            boost_weight: Uint128::zero(),
+            locks_count: 0,
+        });
+        boost_expiry.boost_weight += boost_weight;
+        boost_expiry.locks_count += 1;
+        Ok(())
+    }
+
+    /// Removes the boost weight of a lock from the boost expiry of its unlock time,
+    /// returns false if the boost expiry is already processed
+    pub fn remove_boost_expiry(
+        &mut self,
+        storage: &dyn Storage,
+        unlock_time: u64,
+        boost_weight: Uint128,
+    ) -> StdResult<bool> {
+        let entry = self.boost_expiry(storage, unlock_time)?;
+        if let Some(boost_expiry) = entry {
+            boost_expiry.boost_weight -= boost_weight;
+            boost_expiry.locks_count -= 1;
+            if boost_expiry.locks_count == 0 {
+                *entry = None;
+            }
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Returns the boost checkpoint of the unlock time of an expired lock
+    pub fn boost_checkpoint(
+        &mut self,
+        storage: &dyn Storage,
+        unlock_time: u64,
+    ) -> StdResult<BoostCheckpoint> {
+        let entry = match self.boost_checkpoints.entry(unlock_time) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(BOOST_CHECKPOINTS.may_load(storage, unlock_time)?),
+        };
+        entry
+            .clone()
+            .ok_or_else(|| StdError::not_found("BoostCheckpoint"))
+    }
+
+    /// Releases an expired lock from the boost checkpoint of its unlock time,
+    /// the checkpoint is removed after all its locks are released
+    pub fn release_boost_checkpoint(
+        &mut self,
+        storage: &dyn Storage,
+        unlock_time: u64,
+    ) -> StdResult<()> {
+        let mut boost_checkpoint = self.boost_checkpoint(storage, unlock_time)?;
+        boost_checkpoint.locks_count -= 1;
+        self.boost_checkpoints.insert(
+            unlock_time,
+            Some(boost_checkpoint).filter(|boost_checkpoint| boost_checkpoint.locks_count > 0),
+        );
+        Ok(())
+    }
+
+    /// Writes the loaded phases, boost expiries and checkpoints back to the storage
+    pub fn save(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        for (phase_index, phase_info) in self.loaded.iter() {
+            PHASES_INFO.save(storage, *phase_index, phase_info)?;
+        }
+        for (unlock_time, boost_expiry) in self.boost_expiries.iter() {
+            match boost_expiry {
+                Some(boost_expiry) => BOOST_EXPIRIES.save(storage, *unlock_time, boost_expiry)?,
+                None => BOOST_EXPIRIES.remove(storage, *unlock_time),
+            }
+        }
+        for (unlock_time, boost_checkpoint) in self.boost_checkpoints.iter() {
+            match boost_checkpoint {
+                Some(boost_checkpoint) => {
+                    BOOST_CHECKPOINTS.save(storage, *unlock_time, boost_checkpoint)?
+                }
+                None => BOOST_CHECKPOINTS.remove(storage, *unlock_time),
+            }
+        }
+        Ok(())
+    }
+
+    // Returns the boost expiry of the unlock time, it is loaded from the storage on the first access
+    fn boost_expiry(
+        &mut self,
+        storage: &dyn Storage,
+        unlock_time: u64,
+    ) -> StdResult<&mut Option<BoostExpiry>> {
+        match self.boost_expiries.entry(unlock_time) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                Ok(entry.insert(BOOST_EXPIRIES.may_load(storage, unlock_time)?))
+            }
+        }
+    }
 }
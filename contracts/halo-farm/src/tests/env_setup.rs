@@ -11,7 +11,8 @@ pub mod env {
     use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
 
     use crate::contract::{
-        execute as HaloFarmExecute, instantiate as HaloFarmInstantiate, query as HaloFarmQuery,
+        execute as HaloFarmExecute, instantiate as HaloFarmInstantiate, migrate as HaloFarmMigrate,
+        query as HaloFarmQuery,
     };
 
     pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
@@ -23,6 +24,7 @@ pub mod env {
     pub const NATIVE_DENOM_2: &str = "utaura";
     pub const NATIVE_BALANCE_2: u128 = 1_000_000_000_000u128;
 
+    #[allow(dead_code)]
     pub struct ContractInfo {
         pub contract_addr: String,
         pub contract_code_id: u64,
@@ -51,7 +53,8 @@ pub mod env {
     }
 
     pub fn halo_farm_contract_template() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(HaloFarmExecute, HaloFarmInstantiate, HaloFarmQuery);
+        let contract = ContractWrapper::new(HaloFarmExecute, HaloFarmInstantiate, HaloFarmQuery)
+            .with_migrate(HaloFarmMigrate);
         Box::new(contract)
     }
 
@@ -16,23 +16,25 @@ mod tests {
         use std::str::FromStr;
 
         use crate::state::{
-            FarmInfo, PendingRewardResponse, PhaseInfo, StakerInfoResponse, TokenInfo,
+            FarmInfoResponse, PendingRewardResponse, PhaseInfo, RewardTokenAsset, RewardTokenInfo,
+            StakerInfoResponse, TokenInfo,
         };
         use cosmwasm_std::{
             from_binary, to_binary, Addr, BalanceResponse as BankBalanceResponse, BankQuery,
-            BlockInfo, Coin, Decimal, Querier, QueryRequest, Uint128, WasmQuery,
+            BlockInfo, Coin, Decimal256, Querier, QueryRequest, Uint128, WasmQuery,
         };
         use cw20::{BalanceResponse, Cw20ExecuteMsg};
         use cw_multi_test::Executor;
 
+        use crate::error::ContractError;
         use crate::msg::{
-            ExecuteMsg as FarmExecuteMsg, InstantiateMsg as FarmInstantiateMsg,
-            QueryMsg as FarmQueryMsg,
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
         };
         use crate::tests::{
             env_setup::env::{
-                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_BALANCE_2,
-                NATIVE_DENOM_2, USER_1,
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_BALANCE,
+                NATIVE_BALANCE_2, NATIVE_DENOM, NATIVE_DENOM_2, USER_1,
             },
             integration_test::tests::{
                 ADD_1000_NATIVE_BALANCE_2, INIT_1000_000_NATIVE_BALANCE_2,
@@ -106,13 +108,19 @@ mod tests {
 
             // create farm
             let halo_farm_instantiate_msg = &FarmInstantiateMsg {
-                staked_token: Addr::unchecked(lp_token_contract.clone()),
-                reward_token: native_token_info.clone(),
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
                 start_time: current_block_time,
                 end_time: current_block_time + 100,
                 phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
                 farm_owner: Addr::unchecked(ADMIN.to_string()),
-                whitelist: Addr::unchecked(ADMIN.to_string()),
             };
 
             // instantiate contract
@@ -147,7 +155,7 @@ mod tests {
             assert!(response.is_ok());
 
             // query phases info after adding reward balance
-            let farm_info: FarmInfo = app
+            let farm_info: FarmInfoResponse = app
                 .wrap()
                 .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
                 .unwrap();
@@ -155,40 +163,34 @@ mod tests {
             // assert phases info
             assert_eq!(
                 farm_info,
-                FarmInfo {
-                    staked_token: Addr::unchecked(lp_token_contract.clone()),
-                    reward_token: native_token_info,
+                FarmInfoResponse {
+                    staked_token: TokenInfo::Token {
+                        contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                    },
                     current_phase_index: 0u64,
                     phases_info: vec![PhaseInfo {
                         start_time: current_block_time,
                         end_time: current_block_time + 100,
-                        whitelist: Addr::unchecked(ADMIN.to_string()),
-                        reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                        reward_tokens: vec![RewardTokenInfo {
+                            info: native_token_info.clone(),
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                            reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                            accrued_token_per_share: Decimal256::zero(),
+                            undistributed_reward: Uint128::zero(),
+                            distributed_reward: Uint128::zero(),
+                            claimed_reward: Uint128::zero(),
+                            removed_reward: Uint128::zero(),
+                            emitted_reward: Uint128::zero(),
+                        }],
                         last_reward_time: current_block_time,
-                        accrued_token_per_share: Decimal::zero(),
+                        emission_start_time: None,
                     }],
                     phases_limit_per_user: None,
                     staked_token_balance: Uint128::zero(),
+                    boost_weight: Uint128::zero(),
                 }
             );
 
-            // Approve cw20 token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
-                expires: None,
-            };
-
-            // Execute approve
-            let response = app.execute_contract(
-                Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // increase 1 second to make phase active
             app.set_block(BlockInfo {
                 time: app.block_info().time.plus_seconds(1),
@@ -197,14 +199,19 @@ mod tests {
             });
 
             // deposit lp token to the farm contract
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -265,16 +272,18 @@ mod tests {
             assert_eq!(
                 pending_reward,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(60_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(60_000_000u128),
+                    }],
                     time_query: app.block_info().time.seconds(),
                 }
             );
 
             // Harvest reward
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest
             let response = app.execute_contract(
@@ -298,13 +307,14 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward.amount.u128()
+                        + pending_reward.rewards[0].amount.u128()
                 )
             );
 
             // withdraw some lp token from the farm contract
             let withdraw_msg = FarmExecuteMsg::Withdraw {
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                recipient: None,
             };
 
             // change block time increase 2 seconds to make phase active
@@ -330,10 +340,12 @@ mod tests {
             assert_eq!(
                 pending_reward,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(20_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(20_000_000u128),
+                    }],
                     time_query: 1571797428,
                 }
             );
@@ -378,7 +390,7 @@ mod tests {
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
                         + 60000000u128
-                        + pending_reward.amount.u128()
+                        + pending_reward.rewards[0].amount.u128()
                 )
             );
         }
@@ -518,13 +530,19 @@ mod tests {
 
             // create farm
             let halo_farm_instantiate_msg = &FarmInstantiateMsg {
-                staked_token: Addr::unchecked(lp_token_contract.clone()),
-                reward_token: native_token_info.clone(),
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
                 start_time: current_block_time,
                 end_time: current_block_time + 100,
                 phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
                 farm_owner: Addr::unchecked(ADMIN.to_string()),
-                whitelist: Addr::unchecked(ADMIN.to_string()),
             };
 
             // instantiate contract
@@ -559,7 +577,7 @@ mod tests {
             assert!(response.is_ok());
 
             // query phases info after adding reward balance
-            let farm_info: FarmInfo = app
+            let farm_info: FarmInfoResponse = app
                 .wrap()
                 .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
                 .unwrap();
@@ -567,73 +585,67 @@ mod tests {
             // assert phases info
             assert_eq!(
                 farm_info,
-                FarmInfo {
-                    staked_token: Addr::unchecked(lp_token_contract),
-                    reward_token: native_token_info.clone(),
+                FarmInfoResponse {
+                    staked_token: TokenInfo::Token {
+                        contract_addr: Addr::unchecked(lp_token_contract),
+                    },
                     current_phase_index: 0u64,
                     phases_info: vec![PhaseInfo {
                         start_time: current_block_time,
                         end_time: current_block_time + 100,
-                        whitelist: Addr::unchecked(ADMIN.to_string()),
-                        reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                        reward_tokens: vec![RewardTokenInfo {
+                            info: native_token_info.clone(),
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                            reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                            accrued_token_per_share: Decimal256::zero(),
+                            undistributed_reward: Uint128::zero(),
+                            distributed_reward: Uint128::zero(),
+                            claimed_reward: Uint128::zero(),
+                            removed_reward: Uint128::zero(),
+                            emitted_reward: Uint128::zero(),
+                        }],
                         last_reward_time: current_block_time,
-                        accrued_token_per_share: Decimal::zero(),
+                        emission_start_time: None,
                     }],
                     phases_limit_per_user: None,
                     staked_token_balance: Uint128::zero(),
+                    boost_weight: Uint128::zero(),
                 }
             );
 
-            // Approve cw20 token to farm contract msg
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
-                expires: None,
-            };
-
-            // Execute approve by ADMIN
-            let response = app.execute_contract(
-                Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // Deposit lp token to the farm contract to execute deposit msg
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
-                &deposit_msg,
-                &[],
-            );
-            assert!(response.is_ok());
-
-            // Execute approve by USER_1
-            let response = app.execute_contract(
-                Addr::unchecked(USER_1.to_string()),
                 Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
+                &deposit_msg,
                 &[],
             );
-
             assert!(response.is_ok());
 
             // Deposit lp token to the farm contract to execute deposit msg
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by USER_1
             let response = app.execute_contract(
                 Addr::unchecked(USER_1.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -663,10 +675,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_2s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(13_333_333u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(13_333_333u128),
+                    }],
                     time_query: 1571797421
                 }
             );
@@ -687,16 +701,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user1_2s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(6_666_666u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(6_666_666u128),
+                    }],
                     time_query: 1571797421
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -720,12 +736,12 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_2s.amount.u128()
+                        + pending_reward_admin_2s.rewards[0].amount.u128()
                 )
             );
 
             // Harvest reward by USER_1
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by USER_1
             let response = app.execute_contract(
@@ -748,7 +764,7 @@ mod tests {
 
             assert_eq!(
                 balance.amount.amount,
-                Uint128::from(pending_reward_user1_2s.amount.u128())
+                Uint128::from(pending_reward_user1_2s.rewards[0].amount.u128())
             );
 
             // change block time increase 4 seconds to make 6 seconds passed
@@ -774,10 +790,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_6s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(26_666_666u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(26_666_666u128),
+                    }],
                     time_query: 1571797425
                 }
             );
@@ -785,6 +803,7 @@ mod tests {
             // Withdraw 50% lp token from the farm contract by ADMIN
             let withdraw_msg = FarmExecuteMsg::Withdraw {
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                recipient: None,
             };
 
             // Execute withdraw by ADMIN
@@ -827,8 +846,8 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_2s.amount.u128()
-                        + pending_reward_admin_6s.amount.u128()
+                        + pending_reward_admin_2s.rewards[0].amount.u128()
+                        + pending_reward_admin_6s.rewards[0].amount.u128()
                 )
             );
 
@@ -855,10 +874,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user1_8s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(23_333_333u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(23_333_333u128),
+                    }],
                     time_query: 1571797427
                 }
             );
@@ -866,6 +887,7 @@ mod tests {
             // Withdraw 100% lp token from the farm contract by USER_1
             let withdraw_msg = FarmExecuteMsg::Withdraw {
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                recipient: None,
             };
 
             // Execute withdraw by USER_1
@@ -907,7 +929,8 @@ mod tests {
             assert_eq!(
                 balance.amount.amount,
                 Uint128::from(
-                    pending_reward_user1_2s.amount.u128() + pending_reward_user1_8s.amount.u128()
+                    pending_reward_user1_2s.rewards[0].amount.u128()
+                        + pending_reward_user1_8s.rewards[0].amount.u128()
                 )
             );
 
@@ -934,16 +957,18 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_10s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(30_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(30_000_000u128),
+                    }],
                     time_query: 1571797429
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -968,9 +993,9 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_2s.amount.u128()
-                        + pending_reward_admin_6s.amount.u128()
-                        + pending_reward_admin_10s.amount.u128()
+                        + pending_reward_admin_2s.rewards[0].amount.u128()
+                        + pending_reward_admin_6s.rewards[0].amount.u128()
+                        + pending_reward_admin_10s.rewards[0].amount.u128()
                 )
             );
 
@@ -997,16 +1022,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user_1_10s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::zero(),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::zero(),
+                    }],
                     time_query: 1571797431
                 }
             );
 
             // Harvest reward by USER_1
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by USER_1
             let response = app.execute_contract(
@@ -1017,8 +1044,10 @@ mod tests {
             );
 
             assert_eq!(
-                response.unwrap_err().source().unwrap().to_string(),
-                "Generic error: Unauthorized: Only staker can harvest reward".to_string()
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NotStaker {
+                    address: Addr::unchecked(USER_1)
+                }
             );
 
             // Mint 500 HALO LP tokens to ADMIN
@@ -1037,23 +1066,6 @@ mod tests {
 
             assert!(response.is_ok());
 
-            // Approve cw20 token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
-                expires: None,
-            };
-
-            // Execute approve by ADMIN
-            let response = app.execute_contract(
-                Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // change block time increase 2 seconds to make 14 seconds passed
             app.set_block(BlockInfo {
                 time: app.block_info().time.plus_seconds(2),
@@ -1077,23 +1089,30 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_14s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(40_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(40_000_000u128),
+                    }],
                     time_query: 1571797433
                 }
             );
 
             // Deposit lp token to the farm contract to execute deposit msg
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -1113,10 +1132,10 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_2s.amount.u128()
-                        + pending_reward_admin_6s.amount.u128()
-                        + pending_reward_admin_10s.amount.u128()
-                        + pending_reward_admin_14s.amount.u128()
+                        + pending_reward_admin_2s.rewards[0].amount.u128()
+                        + pending_reward_admin_6s.rewards[0].amount.u128()
+                        + pending_reward_admin_10s.rewards[0].amount.u128()
+                        + pending_reward_admin_14s.rewards[0].amount.u128()
                 )
             );
 
@@ -1128,14 +1147,19 @@ mod tests {
             });
 
             // Deposit 150 lp token to the farm contract by USER_1
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_150_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by USER_1
             let response = app.execute_contract(
                 Addr::unchecked(USER_1.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -1158,10 +1182,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_16s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(20_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(20_000_000u128),
+                    }],
                     time_query: 1571797435
                 }
             );
@@ -1189,16 +1215,18 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_18s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(37_391_305u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(37_391_305u128),
+                    }],
                     time_query: 1571797437
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -1225,12 +1253,12 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_2s.amount.u128()
-                        + pending_reward_admin_6s.amount.u128()
-                        + pending_reward_admin_10s.amount.u128()
-                        + pending_reward_admin_14s.amount.u128()
-                        // + pending_reward_admin_16s.amount.u128() // Did not executed harvest
-                        + pending_reward_admin_18s.amount.u128() // Included pending_reward_admin_16s
+                        + pending_reward_admin_2s.rewards[0].amount.u128()
+                        + pending_reward_admin_6s.rewards[0].amount.u128()
+                        + pending_reward_admin_10s.rewards[0].amount.u128()
+                        + pending_reward_admin_14s.rewards[0].amount.u128()
+                        // + pending_reward_admin_16s.rewards[0].amount.u128() // Did not executed harvest
+                        + pending_reward_admin_18s.rewards[0].amount.u128() // Included pending_reward_admin_16s
                 )
             );
 
@@ -1250,16 +1278,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user_1_18s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(2_608_696u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(2_608_696u128),
+                    }],
                     time_query: 1571797437
                 }
             );
 
             // Harvest reward by USER_1
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by USER_1
             let response = app.execute_contract(
@@ -1285,9 +1315,9 @@ mod tests {
             assert_eq!(
                 balance.amount.amount,
                 Uint128::from(
-                    pending_reward_user1_2s.amount.u128()
-                        + pending_reward_user1_8s.amount.u128()
-                        + pending_reward_user_1_18s.amount.u128()
+                    pending_reward_user1_2s.rewards[0].amount.u128()
+                        + pending_reward_user1_8s.rewards[0].amount.u128()
+                        + pending_reward_user_1_18s.rewards[0].amount.u128()
                 )
             );
 
@@ -1299,7 +1329,10 @@ mod tests {
                 new_end_time: farm_info.phases_info[farm_info.current_phase_index as usize]
                     .end_time
                     + 90,
-                whitelist: Addr::unchecked(ADMIN.to_string()),
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
             };
 
             // Execute extend end time by ADMIN
@@ -1352,16 +1385,18 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_100s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(713_043_478u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(713_043_478u128),
+                    }],
                     time_query: 1571797519
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -1388,13 +1423,13 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_2s.amount.u128()
-                        + pending_reward_admin_6s.amount.u128()
-                        + pending_reward_admin_10s.amount.u128()
-                        + pending_reward_admin_14s.amount.u128()
-                        // + pending_reward_admin_16s.amount.u128() // not execute harvest yet
-                        + pending_reward_admin_18s.amount.u128() // Included pending_reward_admin_16s
-                        + pending_reward_admin_100s.amount.u128()
+                        + pending_reward_admin_2s.rewards[0].amount.u128()
+                        + pending_reward_admin_6s.rewards[0].amount.u128()
+                        + pending_reward_admin_10s.rewards[0].amount.u128()
+                        + pending_reward_admin_14s.rewards[0].amount.u128()
+                        // + pending_reward_admin_16s.rewards[0].amount.u128() // not execute harvest yet
+                        + pending_reward_admin_18s.rewards[0].amount.u128() // Included pending_reward_admin_16s
+                        + pending_reward_admin_100s.rewards[0].amount.u128()
                 )
             );
 
@@ -1414,10 +1449,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user_1_100s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(106_956_522u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(106_956_522u128),
+                    }],
                     time_query: 1571797519
                 }
             );
@@ -1474,7 +1511,7 @@ mod tests {
             assert!(response.is_ok());
 
             // Query phases info after add reward balance
-            let farm_info_1: FarmInfo = app
+            let farm_info_1: FarmInfoResponse = app
                 .wrap()
                 .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
                 .unwrap();
@@ -1482,9 +1519,10 @@ mod tests {
             // assert phases info
             assert_eq!(
                 farm_info_1,
-                FarmInfo {
-                    staked_token: Addr::unchecked(lp_token_contract),
-                    reward_token: native_token_info,
+                FarmInfoResponse {
+                    staked_token: TokenInfo::Token {
+                        contract_addr: Addr::unchecked(lp_token_contract),
+                    },
                     current_phase_index: 1u64,
                     phases_info: vec![
                         PhaseInfo {
@@ -1493,13 +1531,24 @@ mod tests {
                                 .start_time,
                             end_time: farm_info.phases_info[farm_info.current_phase_index as usize]
                                 .end_time,
-                            whitelist: Addr::unchecked(ADMIN.to_string()),
-                            reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                            reward_tokens: vec![RewardTokenInfo {
+                                info: native_token_info.clone(),
+                                whitelist: Addr::unchecked(ADMIN.to_string()),
+                                reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                                accrued_token_per_share: Decimal256::from_str(
+                                    "930434782608695652.173913043478260867",
+                                )
+                                .unwrap(),
+                                undistributed_reward: Uint128::zero(),
+                                distributed_reward: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                                claimed_reward: Uint128::from(893043477u128),
+                                removed_reward: Uint128::zero(),
+                                emitted_reward: Uint128::zero(),
+                            }],
                             last_reward_time: farm_info.phases_info
                                 [farm_info.current_phase_index as usize]
                                 .end_time,
-                            accrued_token_per_share: Decimal::from_str("0.93043478260869565")
-                                .unwrap(),
+                            emission_start_time: None,
                         },
                         PhaseInfo {
                             start_time: farm_info.phases_info
@@ -1509,19 +1558,29 @@ mod tests {
                             end_time: farm_info.phases_info[farm_info.current_phase_index as usize]
                                 .end_time
                                 + 90,
-                            whitelist: Addr::unchecked(ADMIN.to_string()),
-                            reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                            reward_tokens: vec![RewardTokenInfo {
+                                info: native_token_info.clone(),
+                                whitelist: Addr::unchecked(ADMIN.to_string()),
+                                reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                                accrued_token_per_share: Decimal256::zero(),
+                                undistributed_reward: Uint128::zero(),
+                                distributed_reward: Uint128::zero(),
+                                claimed_reward: Uint128::zero(),
+                                removed_reward: Uint128::zero(),
+                                emitted_reward: Uint128::zero(),
+                            }],
                             last_reward_time: farm_info.phases_info
                                 [farm_info.current_phase_index as usize]
                                 .end_time
                                 + 10,
-                            accrued_token_per_share: Decimal::zero(),
+                            emission_start_time: None,
                         }
                     ],
                     phases_limit_per_user: None,
                     staked_token_balance: Uint128::from(
                         MOCK_1000_HALO_LP_TOKEN_AMOUNT + MOCK_150_HALO_LP_TOKEN_AMOUNT
-                    )
+                    ),
+                    boost_weight: Uint128::zero(),
                 }
             );
 
@@ -1548,16 +1607,18 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_135s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(217_391_304u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(217_391_304u128),
+                    }],
                     time_query: 1571797549
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -1600,14 +1661,14 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_2s.amount.u128()
-                        + pending_reward_admin_6s.amount.u128()
-                        + pending_reward_admin_10s.amount.u128()
-                        + pending_reward_admin_14s.amount.u128()
-                        // + pending_reward_admin_16s.amount.u128() // not execute harvest yet
-                        + pending_reward_admin_18s.amount.u128() // Included pending_reward_admin_16s
-                        + pending_reward_admin_100s.amount.u128()
-                        + pending_reward_admin_135s.amount.u128()
+                        + pending_reward_admin_2s.rewards[0].amount.u128()
+                        + pending_reward_admin_6s.rewards[0].amount.u128()
+                        + pending_reward_admin_10s.rewards[0].amount.u128()
+                        + pending_reward_admin_14s.rewards[0].amount.u128()
+                        // + pending_reward_admin_16s.rewards[0].amount.u128() // not execute harvest yet
+                        + pending_reward_admin_18s.rewards[0].amount.u128() // Included pending_reward_admin_16s
+                        + pending_reward_admin_100s.rewards[0].amount.u128()
+                        + pending_reward_admin_135s.rewards[0].amount.u128()
                 )
             );
 
@@ -1629,16 +1690,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user_1_135s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(139_565_217u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(139_565_217u128),
+                    }],
                     time_query: 1571797549
                 }
             );
 
             // Harvest reward by USER_1
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by USER_1
             let response = app.execute_contract(
@@ -1663,11 +1726,11 @@ mod tests {
             assert_eq!(
                 balance.amount.amount,
                 Uint128::from(
-                    pending_reward_user1_2s.amount.u128()
-                        + pending_reward_user1_8s.amount.u128()
-                        + pending_reward_user_1_18s.amount.u128()
-                        // + pending_reward_user_1_100s.amount.u128()
-                        + pending_reward_user_1_135s.amount.u128() // Included pending_reward_user_1_100s
+                    pending_reward_user1_2s.rewards[0].amount.u128()
+                        + pending_reward_user1_8s.rewards[0].amount.u128()
+                        + pending_reward_user_1_18s.rewards[0].amount.u128()
+                        // + pending_reward_user_1_100s.rewards[0].amount.u128()
+                        + pending_reward_user_1_135s.rewards[0].amount.u128() // Included pending_reward_user_1_100s
                 )
             );
             assert_eq!(balance.amount.amount, Uint128::from(172_173_912u128));
@@ -1695,16 +1758,18 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_150s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(163_043_478u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(163_043_478u128),
+                    }],
                     time_query: 1571797564
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -1730,15 +1795,15 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_2s.amount.u128()
-                        + pending_reward_admin_6s.amount.u128()
-                        + pending_reward_admin_10s.amount.u128()
-                        + pending_reward_admin_14s.amount.u128()
-                        // + pending_reward_admin_16s.amount.u128() // not execute harvest yet
-                        + pending_reward_admin_18s.amount.u128() // Included pending_reward_admin_16s
-                        + pending_reward_admin_100s.amount.u128()
-                        + pending_reward_admin_135s.amount.u128()
-                        + pending_reward_admin_150s.amount.u128()
+                        + pending_reward_admin_2s.rewards[0].amount.u128()
+                        + pending_reward_admin_6s.rewards[0].amount.u128()
+                        + pending_reward_admin_10s.rewards[0].amount.u128()
+                        + pending_reward_admin_14s.rewards[0].amount.u128()
+                        // + pending_reward_admin_16s.rewards[0].amount.u128() // not execute harvest yet
+                        + pending_reward_admin_18s.rewards[0].amount.u128() // Included pending_reward_admin_16s
+                        + pending_reward_admin_100s.rewards[0].amount.u128()
+                        + pending_reward_admin_135s.rewards[0].amount.u128()
+                        + pending_reward_admin_150s.rewards[0].amount.u128()
                 )
             );
 
@@ -1760,16 +1825,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user_1_150s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(24_456_522u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(24_456_522u128),
+                    }],
                     time_query: 1571797564
                 }
             );
 
             // Harvest reward by USER_1
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by USER_1
             let response = app.execute_contract(
@@ -1794,12 +1861,12 @@ mod tests {
             assert_eq!(
                 balance.amount.amount,
                 Uint128::from(
-                    pending_reward_user1_2s.amount.u128()
-                        + pending_reward_user1_8s.amount.u128()
-                        + pending_reward_user_1_18s.amount.u128()
-                        // + pending_reward_user_1_100s.amount.u128()
-                        + pending_reward_user_1_135s.amount.u128() // Included pending_reward_user_1_100s
-                        + pending_reward_user_1_150s.amount.u128()
+                    pending_reward_user1_2s.rewards[0].amount.u128()
+                        + pending_reward_user1_8s.rewards[0].amount.u128()
+                        + pending_reward_user_1_18s.rewards[0].amount.u128()
+                        // + pending_reward_user_1_100s.rewards[0].amount.u128()
+                        + pending_reward_user_1_135s.rewards[0].amount.u128() // Included pending_reward_user_1_100s
+                        + pending_reward_user_1_150s.rewards[0].amount.u128()
                 )
             );
             assert_eq!(balance.amount.amount, Uint128::from(196_630_434u128));
@@ -1827,10 +1894,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_155s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(54_347_826u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(54_347_826u128),
+                    }],
                     time_query: 1571797569
                 }
             );
@@ -1838,6 +1907,7 @@ mod tests {
             // Withdraw 50% ADMIN's staked LP amount from farm contract by ADMIN
             let withdraw_msg = FarmExecuteMsg::Withdraw {
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                recipient: None,
             };
 
             // Execute withdraw by ADMIN
@@ -1882,16 +1952,16 @@ mod tests {
                     INIT_1000_000_NATIVE_BALANCE_2
                     - ADD_1000_NATIVE_BALANCE_2
                     - ADD_1000_NATIVE_BALANCE_2
-                    + pending_reward_admin_2s.amount.u128()
-                    + pending_reward_admin_6s.amount.u128()
-                    + pending_reward_admin_10s.amount.u128()
-                    + pending_reward_admin_14s.amount.u128()
-                    // + pending_reward_admin_16s.amount.u128() // not execute harvest yet
-                    + pending_reward_admin_18s.amount.u128() // Included pending_reward_admin_16s
-                    + pending_reward_admin_100s.amount.u128()
-                    + pending_reward_admin_135s.amount.u128()
-                    + pending_reward_admin_150s.amount.u128()
-                    + pending_reward_admin_155s.amount.u128()
+                    + pending_reward_admin_2s.rewards[0].amount.u128()
+                    + pending_reward_admin_6s.rewards[0].amount.u128()
+                    + pending_reward_admin_10s.rewards[0].amount.u128()
+                    + pending_reward_admin_14s.rewards[0].amount.u128()
+                    // + pending_reward_admin_16s.rewards[0].amount.u128() // not execute harvest yet
+                    + pending_reward_admin_18s.rewards[0].amount.u128() // Included pending_reward_admin_16s
+                    + pending_reward_admin_100s.rewards[0].amount.u128()
+                    + pending_reward_admin_135s.rewards[0].amount.u128()
+                    + pending_reward_admin_150s.rewards[0].amount.u128()
+                    + pending_reward_admin_155s.rewards[0].amount.u128()
                 )
             );
 
@@ -1918,16 +1988,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user_1_160s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(22_575_251u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(22_575_251u128),
+                    }],
                     time_query: 1571797574
                 }
             );
 
             // Harvest reward by USER_1
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by USER_1
             let response = app.execute_contract(
@@ -1951,13 +2023,13 @@ mod tests {
             assert_eq!(
                 balance.amount.amount,
                 Uint128::from(
-                    pending_reward_user1_2s.amount.u128()
-                        + pending_reward_user1_8s.amount.u128()
-                        + pending_reward_user_1_18s.amount.u128()
-                        // + pending_reward_user_1_100s.amount.u128()
-                        + pending_reward_user_1_135s.amount.u128() // Included pending_reward_user_1_100s
-                        + pending_reward_user_1_150s.amount.u128()
-                        + pending_reward_user_1_160s.amount.u128()
+                    pending_reward_user1_2s.rewards[0].amount.u128()
+                        + pending_reward_user1_8s.rewards[0].amount.u128()
+                        + pending_reward_user_1_18s.rewards[0].amount.u128()
+                        // + pending_reward_user_1_100s.rewards[0].amount.u128()
+                        + pending_reward_user_1_135s.rewards[0].amount.u128() // Included pending_reward_user_1_100s
+                        + pending_reward_user_1_150s.rewards[0].amount.u128()
+                        + pending_reward_user_1_160s.rewards[0].amount.u128()
                 )
             );
 
@@ -1984,40 +2056,30 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_165s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(96_153_846u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(96_153_846u128),
+                    }],
                     time_query: 1571797579
                 }
             );
 
             // Deposit 500 HALO LP token to the farm contract by ADMIN
-            let deposit_msg = FarmExecuteMsg::Deposit {
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
-            };
-
-            // Approve cw20 token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
-                expires: None,
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
-            // Execute approve by ADMIN
-            let response = app.execute_contract(
-                Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // Execute deposit by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -2047,16 +2109,18 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_170s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(54_347_826u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(54_347_826u128),
+                    }],
                     time_query: 1571797584
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -2083,18 +2147,18 @@ mod tests {
                     INIT_1000_000_NATIVE_BALANCE_2
                     - ADD_1000_NATIVE_BALANCE_2
                     - ADD_1000_NATIVE_BALANCE_2
-                    + pending_reward_admin_2s.amount.u128()
-                    + pending_reward_admin_6s.amount.u128()
-                    + pending_reward_admin_10s.amount.u128()
-                    + pending_reward_admin_14s.amount.u128()
-                    // + pending_reward_admin_16s.amount.u128() // not execute harvest yet
-                    + pending_reward_admin_18s.amount.u128() // Included pending_reward_admin_16s
-                    + pending_reward_admin_100s.amount.u128()
-                    + pending_reward_admin_135s.amount.u128()
-                    + pending_reward_admin_150s.amount.u128()
-                    + pending_reward_admin_155s.amount.u128()
-                    + pending_reward_admin_165s.amount.u128()
-                    + pending_reward_admin_170s.amount.u128()
+                    + pending_reward_admin_2s.rewards[0].amount.u128()
+                    + pending_reward_admin_6s.rewards[0].amount.u128()
+                    + pending_reward_admin_10s.rewards[0].amount.u128()
+                    + pending_reward_admin_14s.rewards[0].amount.u128()
+                    // + pending_reward_admin_16s.rewards[0].amount.u128() // not execute harvest yet
+                    + pending_reward_admin_18s.rewards[0].amount.u128() // Included pending_reward_admin_16s
+                    + pending_reward_admin_100s.rewards[0].amount.u128()
+                    + pending_reward_admin_135s.rewards[0].amount.u128()
+                    + pending_reward_admin_150s.rewards[0].amount.u128()
+                    + pending_reward_admin_155s.rewards[0].amount.u128()
+                    + pending_reward_admin_165s.rewards[0].amount.u128()
+                    + pending_reward_admin_170s.rewards[0].amount.u128()
                 )
             );
         }
@@ -2216,13 +2280,19 @@ mod tests {
 
             // create farm
             let halo_farm_instantiate_msg = &FarmInstantiateMsg {
-                staked_token: Addr::unchecked(lp_token_contract.clone()),
-                reward_token: reward_token_info.clone(),
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: reward_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
                 start_time: current_block_time,
                 end_time: current_block_time + 100,
                 phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
                 farm_owner: Addr::unchecked(ADMIN.to_string()),
-                whitelist: Addr::unchecked(ADMIN.to_string()),
             };
 
             // instantiate contract
@@ -2238,7 +2308,7 @@ mod tests {
                 .unwrap();
 
             // query farm contract address
-            let farm_info: FarmInfo = app
+            let farm_info: FarmInfoResponse = app
                 .wrap()
                 .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
                 .unwrap();
@@ -2246,44 +2316,64 @@ mod tests {
             // assert phases info
             assert_eq!(
                 farm_info,
-                FarmInfo {
-                    staked_token: Addr::unchecked(lp_token_contract),
-                    reward_token: reward_token_info,
+                FarmInfoResponse {
+                    staked_token: TokenInfo::Token {
+                        contract_addr: Addr::unchecked(lp_token_contract),
+                    },
                     current_phase_index: 0u64,
                     phases_info: vec![PhaseInfo {
                         start_time: current_block_time,
                         end_time: current_block_time + 100,
-                        whitelist: Addr::unchecked(ADMIN.to_string()),
-                        reward_balance: Uint128::zero(),
+                        reward_tokens: vec![RewardTokenInfo {
+                            info: reward_token_info.clone(),
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                            reward_balance: Uint128::zero(),
+                            accrued_token_per_share: Decimal256::zero(),
+                            undistributed_reward: Uint128::zero(),
+                            distributed_reward: Uint128::zero(),
+                            claimed_reward: Uint128::zero(),
+                            removed_reward: Uint128::zero(),
+                            emitted_reward: Uint128::zero(),
+                        }],
                         last_reward_time: current_block_time,
-                        accrued_token_per_share: Decimal::zero(),
+                        emission_start_time: None,
                     }],
                     phases_limit_per_user: None,
                     staked_token_balance: Uint128::zero(),
+                    boost_weight: Uint128::zero(),
                 }
             );
 
-            // Increase allowance of reward token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
+            // Deposit reward token to the farm contract
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
-                expires: None,
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
-            // Execute approve by ADMIN
+            // Execute deposit by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
                 Addr::unchecked(reward_token_contract.clone()),
-                &approve_msg,
+                &deposit_msg,
                 &[],
             );
 
-            assert!(response.is_ok());
+            // Only staked token can be deposited
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidStakedToken {
+                    token: Addr::unchecked(reward_token_contract.clone())
+                }
+            );
 
-            // add 1000 reward balance to farm contract
+            // add 1000 NATIVE_2 reward balance to farm contract which only rewards cw20 token
             let add_reward_balance_msg = FarmExecuteMsg::AddRewardBalance {
                 phase_index: 0u64,
-                amount: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
+                amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
             };
 
             // Execute add reward by ADMIN
@@ -2291,37 +2381,52 @@ mod tests {
                 Addr::unchecked(ADMIN.to_string()),
                 halo_farm_contract_addr.clone(),
                 &add_reward_balance_msg,
-                &[],
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
             );
 
-            assert!(response.is_ok());
+            // Only reward tokens of the phase can be added
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidRewardToken {
+                    token: NATIVE_DENOM_2.to_string(),
+                    phase_index: 0
+                }
+            );
 
-            // Increase allowance of lp token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
-                expires: None,
+            // add 1000 reward balance to farm contract
+            let add_reward_balance_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::AddRewardBalance { phase_index: 0u64 }).unwrap(),
             };
 
-            // Execute approve by ADMIN
+            // Execute add reward by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
+                Addr::unchecked(reward_token_contract.clone()),
+                &add_reward_balance_msg,
                 &[],
             );
 
             assert!(response.is_ok());
 
             // Deposit lp token to the farm contract to execute deposit msg
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -2351,16 +2456,18 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_2s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone()),
-                    },
-                    amount: Uint128::from(20_000_000_000_000_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone()),
+                        },
+                        amount: Uint128::from(20_000_000_000_000_000_000u128),
+                    }],
                     time_query: 1571797421,
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -2389,33 +2496,21 @@ mod tests {
                 Uint128::from(20_000_000_000_000_000_000u128)
             );
 
-            // Increase allowance of lp token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
+            // USER_1 deposit 500 HALO LP token to the farm contract
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
-                expires: None,
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
-            // Execute approve by USER_1
+            // Execute deposit by USER_1
             let response = app.execute_contract(
                 Addr::unchecked(USER_1.to_string()),
                 Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
-            // USER_1 deposit 500 HALO LP token to the farm contract
-            let deposit_msg = FarmExecuteMsg::Deposit {
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
-            };
-
-            // Execute deposit by USER_1
-            let response = app.execute_contract(
-                Addr::unchecked(USER_1.to_string()),
-                halo_farm_contract_addr.clone(),
-                &deposit_msg,
+                &deposit_msg,
                 &[],
             );
 
@@ -2444,16 +2539,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user1_4s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone()),
-                    },
-                    amount: Uint128::from(6_666_666_666_666_666_666u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone()),
+                        },
+                        amount: Uint128::from(6_666_666_666_666_666_666u128),
+                    }],
                     time_query: 1571797423,
                 }
             );
 
             // Harvest reward by USER_1
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by USER_1
             let response = app.execute_contract(
@@ -2477,7 +2574,7 @@ mod tests {
                 .unwrap();
 
             // It should be 6,6666x10^18 reward token
-            assert_eq!(balance.balance, pending_reward_user1_4s.amount);
+            assert_eq!(balance.balance, pending_reward_user1_4s.rewards[0].amount);
 
             // change block time increase 2 seconds to make 6 seconds passed
             app.set_block(BlockInfo {
@@ -2502,10 +2599,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_6s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone()),
-                    },
-                    amount: Uint128::from(26_666_666_666_666_666_666u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone()),
+                        },
+                        amount: Uint128::from(26_666_666_666_666_666_666u128),
+                    }],
                     time_query: 1571797425,
                 }
             );
@@ -2513,6 +2612,7 @@ mod tests {
             // Withdraw 500 HALO LP token from the farm contract by ADMIN
             let withdraw_msg = FarmExecuteMsg::Withdraw {
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                recipient: None,
             };
 
             // Execute withdraw by ADMIN
@@ -2540,7 +2640,8 @@ mod tests {
             // It should be 46,6666x10^18 reward token
             assert_eq!(
                 balance.balance,
-                pending_reward_admin_2s.amount + pending_reward_admin_6s.amount
+                pending_reward_admin_2s.rewards[0].amount
+                    + pending_reward_admin_6s.rewards[0].amount
             );
 
             // change block time increase 1 seconds to make 7 seconds passed
@@ -2566,40 +2667,30 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_7s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone()),
-                    },
-                    amount: Uint128::from(5_000_000_000_000_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone()),
+                        },
+                        amount: Uint128::from(5_000_000_000_000_000_000u128),
+                    }],
                     time_query: 1571797426,
                 }
             );
 
-            // Increase allowance of lp token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
-                expires: None,
-            };
-
-            // Execute approve by ADMIN
-            let response = app.execute_contract(
-                Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // deposit 500 HALO LP token to the farm contract by ADMIN
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -2629,16 +2720,18 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_8s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone())
-                    },
-                    amount: Uint128::from(6_666_666_666_666_666_667u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone())
+                        },
+                        amount: Uint128::from(6_666_666_666_666_666_667u128),
+                    }],
                     time_query: 1571797427,
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -2664,10 +2757,10 @@ mod tests {
             // It should be 53,3333x10^18 reward token
             assert_eq!(
                 balance.balance,
-                pending_reward_admin_2s.amount
-                    + pending_reward_admin_6s.amount
-                    + pending_reward_admin_7s.amount
-                    + pending_reward_admin_8s.amount
+                pending_reward_admin_2s.rewards[0].amount
+                    + pending_reward_admin_6s.rewards[0].amount
+                    + pending_reward_admin_7s.rewards[0].amount
+                    + pending_reward_admin_8s.rewards[0].amount
             );
 
             // query pending reward by USER_1 after 8 seconds
@@ -2686,16 +2779,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user1_8s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone())
-                    },
-                    amount: Uint128::from(15_000_000_000_000_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone())
+                        },
+                        amount: Uint128::from(15_000_000_000_000_000_000u128),
+                    }],
                     time_query: 1571797427,
                 }
             );
 
             // harvest reward by USER_1
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by USER_1
             let response = app.execute_contract(
@@ -2721,7 +2816,8 @@ mod tests {
             // It should be 6,6666x10^18 reward token
             assert_eq!(
                 balance.balance,
-                pending_reward_user1_4s.amount + pending_reward_user1_8s.amount
+                pending_reward_user1_4s.rewards[0].amount
+                    + pending_reward_user1_8s.rewards[0].amount
             );
 
             // Query total LP staked by calling TotalStaked query
@@ -2746,7 +2842,10 @@ mod tests {
                 new_end_time: farm_info.phases_info[farm_info.current_phase_index as usize]
                     .end_time
                     + 10,
-                whitelist: Addr::unchecked(ADMIN.to_string()),
+                reward_tokens: vec![PhaseRewardToken {
+                    info: reward_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
             };
 
             // Execute extend end time by ADMIN
@@ -2782,10 +2881,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_100s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone())
-                    },
-                    amount: Uint128::from(613_333_333_333_333_333_333u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone())
+                        },
+                        amount: Uint128::from(613_333_333_333_333_333_333u128),
+                    }],
                     time_query: 1571797519,
                 }
             );
@@ -2807,10 +2908,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user_1_100s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone())
-                    },
-                    amount: Uint128::from(306_666_666_666_666_666_667u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone())
+                        },
+                        amount: Uint128::from(306_666_666_666_666_666_667u128),
+                    }],
                     time_query: 1571797519,
                 }
             );
@@ -2831,33 +2934,17 @@ mod tests {
 
             assert!(response.is_ok());
 
-            // Increase allowance of reward token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
-                expires: None,
-            };
-
-            // Execute approve by ADMIN
-            let response = app.execute_contract(
-                Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(reward_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // add 1000 reward balance to farm contract
-            let add_reward_balance_msg = FarmExecuteMsg::AddRewardBalance {
-                phase_index: 1u64,
+            let add_reward_balance_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::AddRewardBalance { phase_index: 1u64 }).unwrap(),
             };
 
             // Execute add reward by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(reward_token_contract.clone()),
                 &add_reward_balance_msg,
                 &[],
             );
@@ -2900,10 +2987,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_101s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone())
-                    },
-                    amount: Uint128::from(679_999_999_999_999_999_999u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone())
+                        },
+                        amount: Uint128::from(679_999_999_999_999_999_999u128),
+                    }],
                     time_query: 1571797520,
                 }
             );
@@ -2924,10 +3013,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user_1_101s,
                 PendingRewardResponse {
-                    info: TokenInfo::Token {
-                        contract_addr: Addr::unchecked(reward_token_contract.clone())
-                    },
-                    amount: Uint128::from(340_000_000_000_000_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(reward_token_contract.clone())
+                        },
+                        amount: Uint128::from(340_000_000_000_000_000_000u128),
+                    }],
                     time_query: 1571797520,
                 }
             );
@@ -2935,6 +3026,7 @@ mod tests {
             // ADMIN Withdraw 500 HALO LP token to the farm contract
             let withdraw_msg = FarmExecuteMsg::Withdraw {
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                recipient: None,
             };
 
             // Execute withdraw by ADMIN
@@ -2961,12 +3053,12 @@ mod tests {
             // It should be 679,999^18 reward token
             assert_eq!(
                 balance.balance,
-                pending_reward_admin_2s.amount
-                    + pending_reward_admin_6s.amount
-                    + pending_reward_admin_7s.amount
-                    + pending_reward_admin_8s.amount
-                    // + pending_reward_admin_100s.amount
-                    + pending_reward_admin_101s.amount // Included 100s reward token
+                pending_reward_admin_2s.rewards[0].amount
+                    + pending_reward_admin_6s.rewards[0].amount
+                    + pending_reward_admin_7s.rewards[0].amount
+                    + pending_reward_admin_8s.rewards[0].amount
+                    // + pending_reward_admin_100s.rewards[0].amount
+                    + pending_reward_admin_101s.rewards[0].amount // Included 100s reward token
             );
 
             // ADMIN Send 500 HALO LP token to USER_1
@@ -2985,32 +3077,20 @@ mod tests {
 
             assert!(response.is_ok());
 
-            // Increase allowance of lp token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
-                expires: None,
-            };
-
-            // Execute approve by USER_1
-            let response = app.execute_contract(
-                Addr::unchecked(USER_1.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // USER_1 deposit 500 HALO LP token to the farm contract
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by USER_1
             let response = app.execute_contract(
                 Addr::unchecked(USER_1.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -3030,10 +3110,10 @@ mod tests {
 
             assert_eq!(
                 balance.balance,
-                pending_reward_user1_4s.amount
-                    + pending_reward_user1_8s.amount
-                    // + pending_reward_user_1_100s.amount
-                    + pending_reward_user_1_101s.amount // Included 100s reward token
+                pending_reward_user1_4s.rewards[0].amount
+                    + pending_reward_user1_8s.rewards[0].amount
+                    // + pending_reward_user_1_100s.rewards[0].amount
+                    + pending_reward_user_1_101s.rewards[0].amount // Included 100s reward token
             );
 
             // Extend end time by ADMIN more 10 seconds
@@ -3044,7 +3124,10 @@ mod tests {
                 new_end_time: farm_info.phases_info[farm_info.current_phase_index as usize]
                     .end_time
                     + 20,
-                whitelist: Addr::unchecked(ADMIN.to_string()),
+                reward_tokens: vec![PhaseRewardToken {
+                    info: reward_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
             };
 
             // Execute extend end time by ADMIN
@@ -3057,33 +3140,17 @@ mod tests {
 
             assert!(response.is_ok());
 
-            // Increase allowance of reward token to farm contract
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(10_000_000_000_000_000_000u128),
-                expires: None,
-            };
-
-            // Execute approve by ADMIN
-            let response = app.execute_contract(
-                Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(reward_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // Add 10 HALO reward tokens to farm contract
-            let add_reward_balance_msg = FarmExecuteMsg::AddRewardBalance {
-                phase_index: 2u64,
+            let add_reward_balance_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(10_000_000_000_000_000_000u128),
+                msg: to_binary(&FarmCw20HookMsg::AddRewardBalance { phase_index: 2u64 }).unwrap(),
             };
 
             // Execute add reward by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(reward_token_contract.clone()),
                 &add_reward_balance_msg,
                 &[],
             );
@@ -3104,12 +3171,12 @@ mod tests {
             // It should be 679,999^18 reward token
             assert_eq!(
                 balance.balance,
-                pending_reward_admin_2s.amount
-                    + pending_reward_admin_6s.amount
-                    + pending_reward_admin_7s.amount
-                    + pending_reward_admin_8s.amount
-                    // + pending_reward_admin_100s.amount
-                    + pending_reward_admin_101s.amount // Included 100s reward token
+                pending_reward_admin_2s.rewards[0].amount
+                    + pending_reward_admin_6s.rewards[0].amount
+                    + pending_reward_admin_7s.rewards[0].amount
+                    + pending_reward_admin_8s.rewards[0].amount
+                    // + pending_reward_admin_100s.rewards[0].amount
+                    + pending_reward_admin_101s.rewards[0].amount // Included 100s reward token
                     - Uint128::from(10_000_000_000_000_000_000u128) // 10 HALO reward token
             );
 
@@ -3140,12 +3207,12 @@ mod tests {
             // It should be 679,999^18 reward token
             assert_eq!(
                 balance.balance,
-                pending_reward_admin_2s.amount
-                    + pending_reward_admin_6s.amount
-                    + pending_reward_admin_7s.amount
-                    + pending_reward_admin_8s.amount
-                    // + pending_reward_admin_100s.amount
-                    + pending_reward_admin_101s.amount // Included 100s reward token
+                pending_reward_admin_2s.rewards[0].amount
+                    + pending_reward_admin_6s.rewards[0].amount
+                    + pending_reward_admin_7s.rewards[0].amount
+                    + pending_reward_admin_8s.rewards[0].amount
+                    // + pending_reward_admin_100s.rewards[0].amount
+                    + pending_reward_admin_101s.rewards[0].amount // Included 100s reward token
                     - Uint128::from(10_000_000_000_000_000_000u128) // 10 HALO reward token
                     + Uint128::from(10_000_000_000_000_000_000u128) // 10 HALO reward token back
             );
@@ -3255,13 +3322,19 @@ mod tests {
 
             // create farm
             let halo_farm_instantiate_msg = &FarmInstantiateMsg {
-                staked_token: Addr::unchecked(lp_token_contract.clone()),
-                reward_token: native_token_info.clone(),
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
                 start_time: current_block_time,
                 end_time: current_block_time + 10,
                 phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
                 farm_owner: Addr::unchecked(ADMIN.to_string()),
-                whitelist: Addr::unchecked(ADMIN.to_string()),
             };
 
             // instantiate contract
@@ -3296,7 +3369,7 @@ mod tests {
             assert!(response.is_ok());
 
             // query phases info after adding reward balance
-            let farm_info: FarmInfo = app
+            let farm_info: FarmInfoResponse = app
                 .wrap()
                 .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
                 .unwrap();
@@ -3304,20 +3377,31 @@ mod tests {
             // assert phases info
             assert_eq!(
                 farm_info,
-                FarmInfo {
-                    staked_token: Addr::unchecked(lp_token_contract),
-                    reward_token: native_token_info,
+                FarmInfoResponse {
+                    staked_token: TokenInfo::Token {
+                        contract_addr: Addr::unchecked(lp_token_contract),
+                    },
                     current_phase_index: 0u64,
                     phases_info: vec![PhaseInfo {
                         start_time: current_block_time,
                         end_time: current_block_time + 10,
-                        whitelist: Addr::unchecked(ADMIN.to_string()),
-                        reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                        reward_tokens: vec![RewardTokenInfo {
+                            info: native_token_info.clone(),
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                            reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                            accrued_token_per_share: Decimal256::zero(),
+                            undistributed_reward: Uint128::zero(),
+                            distributed_reward: Uint128::zero(),
+                            claimed_reward: Uint128::zero(),
+                            removed_reward: Uint128::zero(),
+                            emitted_reward: Uint128::zero(),
+                        }],
                         last_reward_time: current_block_time,
-                        accrued_token_per_share: Decimal::zero(),
+                        emission_start_time: None,
                     }],
                     phases_limit_per_user: None,
                     staked_token_balance: Uint128::zero(),
+                    boost_weight: Uint128::zero(),
                 }
             );
 
@@ -3328,32 +3412,20 @@ mod tests {
                 chain_id: app.block_info().chain_id,
             });
 
-            // Approve cw20 token to farm contract msg
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT * 10),
-                expires: None,
-            };
-
-            // Execute approve by ADMIN
-            let response = app.execute_contract(
-                Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // Deposit lp token to the farm contract by ADMIN
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -3382,10 +3454,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_8s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(800_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(800_000_000u128),
+                    }],
                     time_query: 1571797429,
                 }
             );
@@ -3399,7 +3473,10 @@ mod tests {
                 new_end_time: farm_info.phases_info[farm_info.current_phase_index as usize]
                     .end_time
                     + 12,
-                whitelist: Addr::unchecked(ADMIN.to_string()),
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
             };
 
             // Execute extend end time by ADMIN
@@ -3483,40 +3560,30 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_14s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(1_000_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(1_000_000_000u128),
+                    }],
                     time_query: 1571797433,
                 }
             );
 
-            // Approve cw20 token to farm contract msg
-            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: halo_farm_contract_addr.to_string(), // Farm Contract
-                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
-                expires: None,
-            };
-
-            // Execute approve by USER_1
-            let response = app.execute_contract(
-                Addr::unchecked(USER_1.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &approve_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // Deposit lp token to the farm contract
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by USER_1
             let response = app.execute_contract(
                 Addr::unchecked(USER_1.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -3546,10 +3613,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_20s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(1_400_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(1_400_000_000u128),
+                    }],
                     time_query: 1571797439,
                 }
             );
@@ -3570,10 +3639,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user1_6s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(200_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(200_000_000u128),
+                    }],
                     time_query: 1571797439,
                 }
             );
@@ -3595,14 +3666,19 @@ mod tests {
             );
 
             // deposit 1000 lp token to the farm contract by ADMIN
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit by ADMIN
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -3622,7 +3698,7 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_20s.amount.u128()
+                        + pending_reward_admin_20s.rewards[0].amount.u128()
                 )
             );
 
@@ -3642,10 +3718,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_harvest,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::zero(),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::zero(),
+                    }],
                     time_query: 1571797439,
                 }
             );
@@ -3666,10 +3744,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user1_6s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(200_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(200_000_000u128),
+                    }],
                     time_query: 1571797439,
                 }
             );
@@ -3697,10 +3777,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user1_8s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(240_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(240_000_000u128),
+                    }],
                     time_query: 1571797441,
                 }
             );
@@ -3721,10 +3803,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_8s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(160_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(160_000_000u128),
+                    }],
                     time_query: 1571797441,
                 }
             );
@@ -3737,7 +3821,7 @@ mod tests {
             });
 
             // Harvest reward by USER_1
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by USER_1
             let response = app.execute_contract(
@@ -3761,7 +3845,7 @@ mod tests {
             // It should be 240 NATIVE_2 as reward is accrued
             assert_eq!(
                 balance.amount.amount,
-                Uint128::from(pending_reward_user1_8s.amount.u128())
+                Uint128::from(pending_reward_user1_8s.rewards[0].amount.u128())
             );
 
             // Increase 1 second to make 25 seconds passed
@@ -3787,10 +3871,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_25s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(160_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(160_000_000u128),
+                    }],
                     time_query: 1571797444,
                 }
             );
@@ -3807,7 +3893,10 @@ mod tests {
             let extend_end_time_msg = FarmExecuteMsg::AddPhase {
                 new_start_time: 1571797448, // 29 seconds
                 new_end_time: 1571797448 + 10,
-                whitelist: Addr::unchecked(ADMIN.to_string()),
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
             };
 
             // Execute extend end time by ADMIN
@@ -3900,10 +3989,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_29s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(160_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(160_000_000u128),
+                    }],
                     time_query: 1571797448,
                 }
             );
@@ -3931,16 +4022,18 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_30s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(240_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(240_000_000u128),
+                    }],
                     time_query: 1571797449,
                 }
             );
 
             // Harvest reward by ADMIN
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest by ADMIN
             let response = app.execute_contract(
@@ -3963,7 +4056,9 @@ mod tests {
 
             assert_eq!(
                 balance.amount.amount,
-                Uint128::from(998_400_000_000u128 + pending_reward_admin_30s.amount.u128())
+                Uint128::from(
+                    998_400_000_000u128 + pending_reward_admin_30s.rewards[0].amount.u128()
+                )
             );
 
             // Increase 10 second to make 40 seconds passed -> Phase 2 ends
@@ -3977,7 +4072,10 @@ mod tests {
             let add_phase_msg = FarmExecuteMsg::AddPhase {
                 new_start_time: 1571797461, // 42 seconds
                 new_end_time: 1571797461 + 10,
-                whitelist: Addr::unchecked(ADMIN.to_string()),
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
             };
 
             // Execute add phase by ADMIN
@@ -4021,7 +4119,7 @@ mod tests {
             assert_eq!(
                 balance.amount.amount,
                 Uint128::from(
-                    998_400_000_000u128 + pending_reward_admin_30s.amount.u128()
+                    998_400_000_000u128 + pending_reward_admin_30s.rewards[0].amount.u128()
                         - ADD_1000_NATIVE_BALANCE_2
                 )
             );
@@ -4057,7 +4155,9 @@ mod tests {
 
             assert_eq!(
                 balance.amount.amount,
-                Uint128::from(998_400_000_000u128 + pending_reward_admin_30s.amount.u128())
+                Uint128::from(
+                    998_400_000_000u128 + pending_reward_admin_30s.rewards[0].amount.u128()
+                )
             );
         }
 
@@ -4180,13 +4280,19 @@ mod tests {
 
             // create farm
             let halo_farm_instantiate_msg = &FarmInstantiateMsg {
-                staked_token: Addr::unchecked(lp_token_contract.clone()),
-                reward_token: native_token_info,
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info,
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
                 start_time: current_block_time + 5,
                 end_time: current_block_time + 5 + 10,
                 phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
                 farm_owner: Addr::unchecked(ADMIN.to_string()),
-                whitelist: Addr::unchecked(ADMIN.to_string()),
             };
 
             // instantiate contract
@@ -4220,33 +4326,6 @@ mod tests {
 
             assert!(response.is_ok());
 
-            // Increase allowence of HALO LP tokens to farm contract
-            let increase_allowance_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
-                spender: Addr::unchecked(halo_farm_contract_addr.clone()).to_string(),
-                amount: Uint128::from(10 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
-                expires: None,
-            };
-
-            // Execute increase allowance by ADMIN
-            let response = app.execute_contract(
-                Addr::unchecked(ADMIN.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &increase_allowance_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
-            // Execute increase allowance by USER_1
-            let response = app.execute_contract(
-                Addr::unchecked(USER_1.to_string()),
-                Addr::unchecked(lp_token_contract.clone()),
-                &increase_allowance_msg,
-                &[],
-            );
-
-            assert!(response.is_ok());
-
             // Increase 1 second to make 1 seconds passed
             app.set_block(BlockInfo {
                 time: app.block_info().time.plus_seconds(1),
@@ -4255,14 +4334,19 @@ mod tests {
             });
 
             // ADMIN deposit 1000 HALO LP tokens to farm contract
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -4277,14 +4361,19 @@ mod tests {
             });
 
             // USER_1 deposit 1000 HALO LP tokens to farm contract
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit
             let response = app.execute_contract(
                 Addr::unchecked(USER_1.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -4314,10 +4403,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_3s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::zero(),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::zero(),
+                    }],
                     time_query: 1571797422
                 }
             );
@@ -4325,6 +4416,7 @@ mod tests {
             // ADMIN withdraw 1000 HALO LP tokens from farm contract
             let withdraw_msg = FarmExecuteMsg::Withdraw {
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                recipient: None,
             };
 
             // Execute withdraw
@@ -4345,14 +4437,19 @@ mod tests {
             });
 
             // ADMIN deposit 1000 HALO LP tokens to farm contract
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -4367,14 +4464,19 @@ mod tests {
             });
 
             // USER_1 deposit 1000 HALO LP tokens to farm contract
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit
             let response = app.execute_contract(
                 Addr::unchecked(USER_1.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -4404,10 +4506,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_6s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(33_333_333u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(33_333_333u128),
+                    }],
                     time_query: 1571797425
                 }
             );
@@ -4428,10 +4532,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user_6s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(66_666_666u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(66_666_666u128),
+                    }],
                     time_query: 1571797425
                 }
             );
@@ -4459,10 +4565,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_7s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(66_666_666u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(66_666_666u128),
+                    }],
                     time_query: 1571797426
                 }
             );
@@ -4483,10 +4591,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user_7s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(133_333_333u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(133_333_333u128),
+                    }],
                     time_query: 1571797426
                 }
             );
@@ -4494,6 +4604,7 @@ mod tests {
             // ADMIN withdraw 500 HALO LP tokens from farm contract
             let withdraw_msg = FarmExecuteMsg::Withdraw {
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                recipient: None,
             };
 
             // Execute withdraw
@@ -4520,7 +4631,7 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_7s.amount.u128() // amount: Uint128::from(66_666_666u128),
+                        + pending_reward_admin_7s.rewards[0].amount.u128() // amount: Uint128::from(66_666_666u128),
                 )
             );
 
@@ -4547,10 +4658,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_8s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(20_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(20_000_000u128),
+                    }],
                     time_query: 1571797427
                 }
             );
@@ -4571,10 +4684,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user_8s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(213_333_333u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(213_333_333u128),
+                    }],
                     time_query: 1571797427
                 }
             );
@@ -4602,10 +4717,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_9s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(40_000_000u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(40_000_000u128),
+                    }],
                     time_query: 1571797428
                 }
             );
@@ -4626,23 +4743,30 @@ mod tests {
             assert_eq!(
                 pending_reward_user_9s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(293_333_333u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(293_333_333u128),
+                    }],
                     time_query: 1571797428
                 }
             );
 
             // ADMIN deposit 1000 HALO LP tokens to farm contract
-            let deposit_msg = FarmExecuteMsg::Deposit {
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
                 amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
             };
 
             // Execute deposit
             let response = app.execute_contract(
                 Addr::unchecked(ADMIN.to_string()),
-                halo_farm_contract_addr.clone(),
+                Addr::unchecked(lp_token_contract.clone()),
                 &deposit_msg,
                 &[],
             );
@@ -4672,10 +4796,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_10s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(42_857_143u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(42_857_143u128),
+                    }],
                     time_query: 1571797429
                 }
             );
@@ -4696,10 +4822,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user_10s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(350_476_190u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(350_476_190u128),
+                    }],
                     time_query: 1571797429
                 }
             );
@@ -4727,10 +4855,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_11s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(85_714_286u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(85_714_286u128),
+                    }],
                     time_query: 1571797430
                 }
             );
@@ -4751,16 +4881,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user_11s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(407_619_047u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(407_619_047u128),
+                    }],
                     time_query: 1571797430
                 }
             );
 
             // ADMIN harvest reward
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest
             let response = app.execute_contract(
@@ -4785,9 +4917,9 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_7s.amount.u128()
-                        + pending_reward_admin_9s.amount.u128() // Uint128::from(293_333_333u128),
-                        + pending_reward_admin_11s.amount.u128() // Uint128::from(85_714_286u128),
+                        + pending_reward_admin_7s.rewards[0].amount.u128()
+                        + pending_reward_admin_9s.rewards[0].amount.u128() // Uint128::from(293_333_333u128),
+                        + pending_reward_admin_11s.rewards[0].amount.u128() // Uint128::from(85_714_286u128),
                 )
             );
 
@@ -4814,10 +4946,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_12s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(42_857_143u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(42_857_143u128),
+                    }],
                     time_query: 1571797431
                 }
             );
@@ -4838,10 +4972,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user_12s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(464_761_904u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(464_761_904u128),
+                    }],
                     time_query: 1571797431
                 }
             );
@@ -4869,10 +5005,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_15s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(171_428_572u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(171_428_572u128),
+                    }],
                     time_query: 1571797434
                 }
             );
@@ -4893,10 +5031,12 @@ mod tests {
             assert_eq!(
                 pending_reward_user_15s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(636_190_476u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(636_190_476u128),
+                    }],
                     time_query: 1571797434
                 }
             );
@@ -4924,10 +5064,12 @@ mod tests {
             assert_eq!(
                 pending_reward_admin_16s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(171_428_572u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(171_428_572u128),
+                    }],
                     time_query: 1571797435
                 }
             );
@@ -4937,6 +5079,7 @@ mod tests {
                 amount: Uint128::from(
                     MOCK_1000_HALO_LP_TOKEN_AMOUNT + MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2,
                 ),
+                recipient: None,
             };
 
             // Execute withdraw
@@ -4962,10 +5105,10 @@ mod tests {
                 balance.amount.amount,
                 Uint128::from(
                     INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
-                        + pending_reward_admin_7s.amount.u128()
-                        + pending_reward_admin_9s.amount.u128()
-                        + pending_reward_admin_11s.amount.u128()
-                        + pending_reward_admin_16s.amount.u128()
+                        + pending_reward_admin_7s.rewards[0].amount.u128()
+                        + pending_reward_admin_9s.rewards[0].amount.u128()
+                        + pending_reward_admin_11s.rewards[0].amount.u128()
+                        + pending_reward_admin_16s.rewards[0].amount.u128()
                 )
             );
 
@@ -4992,16 +5135,18 @@ mod tests {
             assert_eq!(
                 pending_reward_user_17s,
                 PendingRewardResponse {
-                    info: TokenInfo::NativeToken {
-                        denom: NATIVE_DENOM_2.to_string()
-                    },
-                    amount: Uint128::from(636_190_476u128),
+                    rewards: vec![RewardTokenAsset {
+                        info: TokenInfo::NativeToken {
+                            denom: NATIVE_DENOM_2.to_string()
+                        },
+                        amount: Uint128::from(636_190_476u128),
+                    }],
                     time_query: 1571797436
                 }
             );
 
             // USER_1 harvest reward
-            let harvest_msg = FarmExecuteMsg::Harvest {};
+            let harvest_msg = FarmExecuteMsg::Harvest { recipient: None };
 
             // Execute harvest
             let response = app.execute_contract(
@@ -5024,8 +5169,7638 @@ mod tests {
 
             assert_eq!(
                 balance.amount.amount,
-                Uint128::from(pending_reward_user_17s.amount.u128())
+                Uint128::from(pending_reward_user_17s.rewards[0].amount.u128())
+            );
+        }
+
+        // Create farm contract with native staked token NATIVE and native reward token NATIVE_2
+        // Add 1000 NATIVE_2 reward balance amount to farm contract by ADMIN
+        // with end time 100 seconds -> 10 NATIVE_2 per second
+        // Deposit 1000 NATIVE to the farm contract by ADMIN via funds
+        // Withdraw 1000 NATIVE from the farm contract by ADMIN after 6 seconds
+        // -> Reward balance: 6s: 6 * 10 = 60 NATIVE_2
+        #[test]
+        fn proper_operation_with_native_staked_token() {
+            // get integration test app and contracts
+            let (mut app, _contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::NativeToken {
+                    denom: NATIVE_DENOM.to_string(),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // add reward balance to farm contract
+            let add_reward_balance_msg = FarmExecuteMsg::AddRewardBalance {
+                phase_index: 0u64,
+                amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &add_reward_balance_msg,
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 1 second to make phase active
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // deposit native staked token to the farm contract
+            let deposit_msg = FarmExecuteMsg::Deposit {
+                lock_duration: None,
+            };
+
+            // Execute deposit without funds
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &deposit_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().source().unwrap().to_string(),
+                "No funds sent".to_string()
+            );
+
+            // Execute deposit with 1000 NATIVE
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &deposit_msg,
+                &[Coin {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    denom: NATIVE_DENOM.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // query staker info of ADMIN
+            let staker_info: StakerInfoResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::StakerInfo {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                staker_info,
+                StakerInfoResponse {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    joined_phase: 0u64,
+                }
+            );
+
+            // query balance of farm contract in native staked token
+            let balance = app
+                .wrap()
+                .query_balance(halo_farm_contract_addr.clone(), NATIVE_DENOM)
+                .unwrap();
+
+            assert_eq!(
+                balance.amount,
+                Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT)
+            );
+
+            // change block time increase 6 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(6),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // withdraw all native staked token from the farm contract
+            let withdraw_msg = FarmExecuteMsg::Withdraw {
+                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                recipient: None,
+            };
+
+            // Execute withdraw
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &withdraw_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // query balance of ADMIN in native staked token
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM).unwrap();
+
+            // It should be the initial balance as all staked token is withdrawn
+            assert_eq!(balance.amount, Uint128::from(NATIVE_BALANCE));
+
+            // query balance of farm contract in native staked token
+            let balance = app
+                .wrap()
+                .query_balance(halo_farm_contract_addr, NATIVE_DENOM)
+                .unwrap();
+
+            assert_eq!(balance.amount, Uint128::zero());
+
+            // query balance of ADMIN in native reward token
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM_2).unwrap();
+
+            // It should be increased 60 NATIVE_2 as reward is harvested
+            assert_eq!(
+                balance.amount,
+                Uint128::from(INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2)
+                    + Uint128::from(60_000_000u128)
+            );
+        }
+
+        // Create farm contract with 2 reward tokens: NATIVE_2 whitelisted for ADMIN
+        // and cw20 HALO reward token whitelisted for USER_1
+        // Add 1000 NATIVE_2 reward balance by ADMIN -> 10 NATIVE_2 per second
+        // Add 1000 HALO reward balance by USER_1 -> 10 HALO per second
+        // Deposit 1000 lp token to the farm contract by ADMIN
+        // Harvest reward by ADMIN after 6 seconds -> 60 NATIVE_2 and 60 HALO
+        #[test]
+        fn proper_operation_with_multiple_reward_tokens() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+            // get halo reward token contract
+            let reward_token_contract = &contracts[1].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &mint_msg,
+                &[],
             );
+
+            assert!(response.is_ok());
+
+            // Mint 1000 HALO reward tokens to ADMIN and USER_1
+            for recipient in [ADMIN, USER_1] {
+                let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
+                };
+
+                // Execute minting
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(reward_token_contract.clone()),
+                    &mint_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // native token info
+            let native_token_info = TokenInfo::NativeToken {
+                denom: NATIVE_DENOM_2.to_string(),
+            };
+
+            // reward token info
+            let reward_token_info = TokenInfo::Token {
+                contract_addr: Addr::unchecked(reward_token_contract.clone()),
+            };
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![
+                    PhaseRewardToken {
+                        info: native_token_info.clone(),
+                        whitelist: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    PhaseRewardToken {
+                        info: reward_token_info.clone(),
+                        whitelist: Addr::unchecked(USER_1.to_string()),
+                    },
+                ],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // add 1000 NATIVE_2 reward balance to farm contract
+            let add_reward_balance_msg = FarmExecuteMsg::AddRewardBalance {
+                phase_index: 0u64,
+                amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+            };
+
+            // Execute add reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &add_reward_balance_msg,
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 HALO reward balance to farm contract
+            let add_reward_balance_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::AddRewardBalance { phase_index: 0u64 }).unwrap(),
+            };
+
+            // Execute add reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(reward_token_contract.clone()),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            // Only USER_1 is whitelisted to add HALO reward balance
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NotWhitelisted {
+                    sender: Addr::unchecked(ADMIN)
+                }
+            );
+
+            // Execute add reward balance by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(reward_token_contract.clone()),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // query farm info after adding reward balance
+            let farm_info: FarmInfoResponse = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+                .unwrap();
+
+            assert_eq!(
+                farm_info.phases_info[0].reward_tokens,
+                vec![
+                    RewardTokenInfo {
+                        info: native_token_info.clone(),
+                        whitelist: Addr::unchecked(ADMIN.to_string()),
+                        reward_balance: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                        accrued_token_per_share: Decimal256::zero(),
+                        undistributed_reward: Uint128::zero(),
+                        distributed_reward: Uint128::zero(),
+                        claimed_reward: Uint128::zero(),
+                        removed_reward: Uint128::zero(),
+                        emitted_reward: Uint128::zero(),
+                    },
+                    RewardTokenInfo {
+                        info: reward_token_info.clone(),
+                        whitelist: Addr::unchecked(USER_1.to_string()),
+                        reward_balance: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
+                        accrued_token_per_share: Decimal256::zero(),
+                        undistributed_reward: Uint128::zero(),
+                        distributed_reward: Uint128::zero(),
+                        claimed_reward: Uint128::zero(),
+                        removed_reward: Uint128::zero(),
+                        emitted_reward: Uint128::zero(),
+                    },
+                ]
+            );
+
+            // increase 1 second to make phase active
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Deposit lp token to the farm contract to execute deposit msg
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
+            };
+
+            // Execute deposit by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 6 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(6),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query pending reward of ADMIN
+            let pending_reward: PendingRewardResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::PendingReward {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            // It should be 60 NATIVE_2 and 60 HALO as reward is accrued
+            assert_eq!(
+                pending_reward,
+                PendingRewardResponse {
+                    rewards: vec![
+                        RewardTokenAsset {
+                            info: native_token_info,
+                            amount: Uint128::from(60_000_000u128),
+                        },
+                        RewardTokenAsset {
+                            info: reward_token_info,
+                            amount: Uint128::from(60_000_000_000_000_000_000u128),
+                        },
+                    ],
+                    time_query: app.block_info().time.seconds(),
+                }
+            );
+
+            // Harvest reward by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // query balance of ADMIN in native token
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(
+                balance.amount,
+                Uint128::from(INIT_1000_000_NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2)
+                    + pending_reward.rewards[0].amount
+            );
+
+            // query balance of ADMIN in HALO reward token
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    reward_token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                balance.balance,
+                Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT)
+                    + pending_reward.rewards[1].amount
+            );
+        }
+    }
+
+    // create farm contract
+    // execute invalid operations on the farm contract
+    // each operation should fail with the matching contract error
+    mod execute_invalid_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Uint128};
+        use cw20::Cw20ExecuteMsg;
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken,
+        };
+        use crate::state::TokenInfo;
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Create farm contract with invalid time range -> InvalidTimeRange
+        // Create farm contract with phases limit per user 1000 LP and NATIVE_2 reward token
+        // Deposit before adding reward balance -> EmptyPhase
+        // Add phase by USER_1 -> Unauthorized
+        // Add reward balance with mismatched funds -> FundsMismatch
+        // Deposit native token to cw20 staked farm -> Cw20StakedToken
+        // Deposit 1500 LP by ADMIN -> ExceedsUserLimit
+        // Withdraw by USER_1 -> NotStaker
+        // Withdraw 0 and 1500 LP by ADMIN -> InvalidZeroAmount, InsufficientStake
+        // Harvest before start time -> NoReward
+        // Add reward balance after start time -> PhaseStarted
+        // Activate phase without new phase -> NoPhaseToActivate
+        // Add phase overlapping the current phase -> PhaseOverlap
+        #[test]
+        fn execute_with_typed_errors() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 2000 HALO LP tokens to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let reward_tokens = vec![PhaseRewardToken {
+                info: TokenInfo::NativeToken {
+                    denom: NATIVE_DENOM_2.to_string(),
+                },
+                whitelist: Addr::unchecked(ADMIN.to_string()),
+            }];
+
+            // create farm with start time equal to end time
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: reward_tokens.clone(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 10,
+                phases_limit_per_user: Some(Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT)),
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let response = app.instantiate_contract(
+                halo_farm_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &halo_farm_instantiate_msg,
+                &[],
+                "instantiate contract",
+                None,
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidTimeRange {
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 10,
+                }
+            );
+
+            // create farm with valid time range
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                end_time: current_block_time + 110,
+                ..halo_farm_instantiate_msg.clone()
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Deposit lp token to the farm contract to execute deposit msg
+            let deposit_msg = |amount: u128| Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
+            };
+
+            // Execute deposit by ADMIN before adding reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::EmptyPhase { phase_index: 0 }
+            );
+
+            // Execute add phase by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddPhase {
+                    new_start_time: current_block_time + 110,
+                    new_end_time: current_block_time + 210,
+                    reward_tokens: reward_tokens.clone(),
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Unauthorized {}
+            );
+
+            // add 1000 NATIVE_2 reward balance to farm contract
+            let add_reward_balance_msg = FarmExecuteMsg::AddRewardBalance {
+                phase_index: 0u64,
+                amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+            };
+
+            // Execute add reward balance by ADMIN with only 500 NATIVE_2 sent
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &add_reward_balance_msg,
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2 / 2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::FundsMismatch {
+                    expected: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    received: Uint128::from(ADD_1000_NATIVE_BALANCE_2 / 2),
+                }
+            );
+
+            // Execute add reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &add_reward_balance_msg,
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute native deposit by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Deposit {
+                    lock_duration: None,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Cw20StakedToken {}
+            );
+
+            // Execute deposit 1500 LP by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(MOCK_1000_HALO_LP_TOKEN_AMOUNT * 3 / 2),
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::ExceedsUserLimit {
+                    limit: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    requested: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT * 3 / 2),
+                }
+            );
+
+            // Execute deposit 1000 LP by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute withdraw by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NotStaker {
+                    address: Addr::unchecked(USER_1)
+                }
+            );
+
+            // Execute withdraw 0 LP by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::zero(),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidZeroAmount {}
+            );
+
+            // Execute withdraw 1500 LP by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT * 3 / 2),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InsufficientStake {
+                    staked: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    requested: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT * 3 / 2),
+                }
+            );
+
+            // Execute harvest by ADMIN before start time
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoReward {}
+            );
+
+            // change block time increase 20 seconds to make phase active
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(20),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute add reward balance by ADMIN after start time
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &add_reward_balance_msg,
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::PhaseStarted {
+                    phase_index: 0,
+                    start_time: current_block_time + 10,
+                }
+            );
+
+            // Execute activate phase by ADMIN without adding new phase
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::ActivatePhase {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoPhaseToActivate {}
+            );
+
+            // Execute add phase by ADMIN starting before end time of the current phase
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::AddPhase {
+                    new_start_time: current_block_time + 100,
+                    new_end_time: current_block_time + 200,
+                    reward_tokens,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::PhaseOverlap {
+                    new_start_time: current_block_time + 100,
+                    end_time: current_block_time + 110,
+                }
+            );
+        }
+    }
+
+    // create farm contract
+    // deposit some lp token to the farm contract
+    // migrate the farm contract in the middle of the phase
+    mod migrate_operation {
+        use cosmwasm_std::{
+            to_binary, Addr, BlockInfo, Coin, Decimal, Decimal256, Order, StdResult, Storage,
+            Timestamp, Uint128, Uint256,
+        };
+        use cosmwasm_storage::PrefixedStorage;
+        use cw2::{query_contract_info, set_contract_version};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg};
+        use cw_multi_test::{App, Executor};
+
+        use crate::error::ContractError;
+        use crate::formulas::ACCRUED_TOKEN_PER_SHARE_SCALE;
+        use crate::migrations::{v1_0_0, v1_1_0, v1_2_0};
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, MigrateMsg, PhaseRewardToken,
+            QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{
+            FarmInfoResponse, LockTier, PendingRewardResponse, TokenInfo, BOOST_CHECKPOINTS,
+            FARM_INFO, PHASES_INFO, STAKERS_INFO,
+        };
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{
+                MOCK_1000_HALO_LP_TOKEN_AMOUNT, MOCK_1000_HALO_REWARD_TOKEN_AMOUNT,
+            },
+        };
+
+        // Write the contract storage of the farm like it was written by the given version
+        fn set_farm_storage<F>(app: &mut App, farm: &Addr, write_fn: F)
+        where
+            F: FnOnce(&mut dyn Storage),
+        {
+            app.init_modules(|_, _, storage| {
+                let mut namespace = b"contract_data/".to_vec();
+                namespace.extend_from_slice(farm.as_bytes());
+                let mut contract_storage =
+                    PrefixedStorage::multilevel(storage, &[b"wasm", &namespace]);
+                write_fn(&mut contract_storage);
+            });
+        }
+
+        // Rewrite the current state to the state layout of version 1.3.0
+        fn downgrade_to_v1_3_0(storage: &mut dyn Storage) {
+            let phases_info = PHASES_INFO
+                .range(storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            for (phase_index, mut phase_info) in phases_info {
+                for reward_token in phase_info.reward_tokens.iter_mut() {
+                    reward_token.accrued_token_per_share = Decimal256::new(
+                        reward_token.accrued_token_per_share.atomics()
+                            / Uint256::from(ACCRUED_TOKEN_PER_SHARE_SCALE),
+                    );
+                }
+                PHASES_INFO.save(storage, phase_index, &phase_info).unwrap();
+            }
+
+            let boost_checkpoints = BOOST_CHECKPOINTS
+                .range(storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            for (unlock_time, mut boost_checkpoint) in boost_checkpoints {
+                for accrued_token_per_share in boost_checkpoint.accrued_token_per_share.iter_mut() {
+                    *accrued_token_per_share = Decimal256::new(
+                        accrued_token_per_share.atomics()
+                            / Uint256::from(ACCRUED_TOKEN_PER_SHARE_SCALE),
+                    );
+                }
+                BOOST_CHECKPOINTS
+                    .save(storage, unlock_time, &boost_checkpoint)
+                    .unwrap();
+            }
+
+            set_contract_version(storage, "crates.io:halo-farm", "1.3.0").unwrap();
+        }
+
+        // Rewrite the current state to the state layout of version 1.2.0
+        fn downgrade_to_v1_2_0(storage: &mut dyn Storage) {
+            downgrade_to_v1_3_0(storage);
+            let stakers_info = STAKERS_INFO
+                .range(storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            for (staker, staker_info) in stakers_info {
+                // The reward debt of the phases before the joined phase is empty
+                let mut reward_debt = vec![vec![]; staker_info.joined_phase as usize];
+                reward_debt.push(
+                    staker_info
+                        .reward_debt
+                        .into_iter()
+                        .map(|reward_debt| Uint128::try_from(reward_debt).unwrap())
+                        .collect(),
+                );
+
+                let legacy_staker_info = v1_2_0::StakerInfo {
+                    amount: staker_info.amount,
+                    reward_debt,
+                    joined_phase: staker_info.joined_phase,
+                    locks: staker_info.locks,
+                    boost_weight: staker_info.boost_weight,
+                    unpaid_rewards: staker_info.unpaid_rewards,
+                };
+                v1_2_0::STAKERS_INFO
+                    .save(storage, staker, &legacy_staker_info)
+                    .unwrap();
+            }
+
+            set_contract_version(storage, "crates.io:halo-farm", "1.2.0").unwrap();
+        }
+
+        // Rewrite the current state to the state layout of version 1.1.0
+        fn downgrade_to_v1_1_0(storage: &mut dyn Storage) {
+            downgrade_to_v1_2_0(storage);
+            let farm_info = FARM_INFO.load(storage).unwrap();
+
+            let mut phases_info = vec![];
+            for phase_index in 0..farm_info.phases_count {
+                phases_info.push(PHASES_INFO.load(storage, phase_index).unwrap());
+                PHASES_INFO.remove(storage, phase_index);
+            }
+
+            let legacy_farm_info = v1_1_0::FarmInfo {
+                staked_token: farm_info.staked_token,
+                current_phase_index: farm_info.current_phase_index,
+                phases_info,
+                phases_limit_per_user: farm_info.phases_limit_per_user,
+                staked_token_balance: farm_info.staked_token_balance,
+                boost_weight: farm_info.boost_weight,
+            };
+            v1_1_0::FARM_INFO.save(storage, &legacy_farm_info).unwrap();
+
+            set_contract_version(storage, "crates.io:halo-farm", "1.1.0").unwrap();
+        }
+
+        // Rewrite the current state to the state layout of version 1.0.0
+        fn downgrade_to_v1_0_0(storage: &mut dyn Storage) {
+            downgrade_to_v1_1_0(storage);
+            let farm_info = v1_1_0::FARM_INFO.load(storage).unwrap();
+            let reward_token = farm_info.phases_info[0].reward_tokens[0].info.clone();
+
+            let legacy_farm_info = v1_0_0::FarmInfo {
+                staked_token: match farm_info.staked_token {
+                    TokenInfo::Token { contract_addr } => contract_addr,
+                    TokenInfo::NativeToken { .. } => {
+                        panic!("v1.0.0 only supports cw20 staked token")
+                    }
+                },
+                reward_token,
+                current_phase_index: farm_info.current_phase_index,
+                phases_info: farm_info
+                    .phases_info
+                    .into_iter()
+                    .map(|phase_info| v1_0_0::PhaseInfo {
+                        start_time: phase_info.start_time,
+                        end_time: phase_info.end_time,
+                        whitelist: phase_info.reward_tokens[0].whitelist.clone(),
+                        reward_balance: phase_info.reward_tokens[0].reward_balance,
+                        last_reward_time: phase_info.last_reward_time,
+                        accrued_token_per_share: Decimal::new(
+                            Uint128::try_from(
+                                phase_info.reward_tokens[0]
+                                    .accrued_token_per_share
+                                    .atomics(),
+                            )
+                            .unwrap(),
+                        ),
+                    })
+                    .collect(),
+                phases_limit_per_user: farm_info.phases_limit_per_user,
+                staked_token_balance: farm_info.staked_token_balance,
+            };
+            v1_0_0::FARM_INFO.save(storage, &legacy_farm_info).unwrap();
+
+            let stakers_info = v1_2_0::STAKERS_INFO
+                .range(storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            for (staker, staker_info) in stakers_info {
+                let legacy_staker_info = v1_0_0::StakerInfo {
+                    amount: staker_info.amount,
+                    reward_debt: staker_info
+                        .reward_debt
+                        .into_iter()
+                        .map(|reward_debt| reward_debt[0])
+                        .collect(),
+                    joined_phase: staker_info.joined_phase,
+                };
+                v1_0_0::STAKERS_INFO
+                    .save(storage, staker, &legacy_staker_info)
+                    .unwrap();
+            }
+
+            set_contract_version(storage, "crates.io:halo-farm", "1.0.0").unwrap();
+        }
+
+        // Create farm contract with 1000 HALO reward balance -> 10 HALO per second
+        // Deposit 1000 lp token to the farm contract by ADMIN
+        // Deposit 1000 lp token to the farm contract by USER_1 after 10 seconds
+        // Query pending reward after 10 seconds -> ADMIN: 150 HALO, USER_1: 50 HALO
+        // Rewrite the farm state to the layout of version 1.0.0
+        // Migrate the farm contract -> pending rewards are not changed
+        // Migrate to an older version or from another contract -> error
+        // Harvest reward by USER_1 after 10 seconds -> 100 HALO
+        #[test]
+        fn proper_migrate_from_v1_0_0_in_the_middle_of_phase() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+            // get halo reward token contract
+            let reward_token_contract = &contracts[1].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN and USER_1
+            for recipient in [ADMIN, USER_1] {
+                let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                };
+
+                // Execute minting
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &mint_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // Mint 1000 HALO reward tokens to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(reward_token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm with ADMIN as the contract admin to be able to migrate
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::Token {
+                        contract_addr: Addr::unchecked(reward_token_contract.clone()),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    Some(ADMIN.to_string()),
+                )
+                .unwrap();
+
+            // add 1000 HALO reward balance to farm contract
+            let add_reward_balance_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_REWARD_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::AddRewardBalance { phase_index: 0u64 }).unwrap(),
+            };
+
+            // Execute add reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(reward_token_contract.clone()),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Deposit 1000 lp token to the farm contract by ADMIN and USER_1 10 seconds apart
+            for (depositor, seconds) in [(ADMIN, 1), (USER_1, 10)] {
+                // increase block time before depositing
+                app.set_block(BlockInfo {
+                    time: app.block_info().time.plus_seconds(seconds),
+                    height: app.block_info().height + 1,
+                    chain_id: app.block_info().chain_id,
+                });
+
+                let deposit_msg = Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                };
+
+                // Execute deposit
+                let response = app.execute_contract(
+                    Addr::unchecked(depositor.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &deposit_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // change block time increase 10 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query pending reward of ADMIN and USER_1 before migration
+            let query_pending_rewards = |app: &App| -> StdResult<Vec<PendingRewardResponse>> {
+                [ADMIN, USER_1]
+                    .iter()
+                    .map(|address| {
+                        app.wrap().query_wasm_smart(
+                            halo_farm_contract_addr.clone(),
+                            &FarmQueryMsg::PendingReward {
+                                address: address.to_string(),
+                            },
+                        )
+                    })
+                    .collect()
+            };
+            let pending_rewards = query_pending_rewards(&app).unwrap();
+
+            // ADMIN: 10 seconds * 10 HALO + 10 seconds * 5 HALO, USER_1: 10 seconds * 5 HALO
+            assert_eq!(
+                pending_rewards[0].rewards[0].amount,
+                Uint128::from(150_000_000_000_000_000_000u128)
+            );
+            assert_eq!(
+                pending_rewards[1].rewards[0].amount,
+                Uint128::from(50_000_000_000_000_000_000u128)
+            );
+
+            // Rewrite the farm state as it was deployed with version 1.0.0
+            set_farm_storage(&mut app, &halo_farm_contract_addr, downgrade_to_v1_0_0);
+
+            // The new code can not read the state of version 1.0.0
+            assert!(query_pending_rewards(&app).is_err());
+
+            // Migrate the farm contract
+            let response = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                halo_farm_contract_addr.clone(),
+                &MigrateMsg {},
+                halo_farm_contract_code_id,
+            );
+
+            assert!(response.is_ok());
+
+            // Pending rewards are not changed after migration
+            assert_eq!(query_pending_rewards(&app).unwrap(), pending_rewards);
+
+            // Contract version is updated
+            let contract_version =
+                query_contract_info(&app.wrap(), halo_farm_contract_addr.clone()).unwrap();
+            assert_eq!(contract_version.version, env!("CARGO_PKG_VERSION"));
+
+            // Set the stored version to a newer version
+            set_farm_storage(&mut app, &halo_farm_contract_addr, |storage| {
+                set_contract_version(storage, "crates.io:halo-farm", "99.0.0").unwrap();
+            });
+
+            // Migrate the farm contract to an older version
+            let response = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                halo_farm_contract_addr.clone(),
+                &MigrateMsg {},
+                halo_farm_contract_code_id,
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::MigrationDowngrade {
+                    stored: "99.0.0".to_string(),
+                    new: env!("CARGO_PKG_VERSION").to_string(),
+                }
+            );
+
+            // Set the stored contract name to another contract
+            set_farm_storage(&mut app, &halo_farm_contract_addr, |storage| {
+                set_contract_version(storage, "crates.io:cw20-base", "1.0.0").unwrap();
+            });
+
+            // Migrate the farm contract from another contract
+            let response = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                halo_farm_contract_addr.clone(),
+                &MigrateMsg {},
+                halo_farm_contract_code_id,
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidContractName {
+                    expected: "crates.io:halo-farm".to_string(),
+                    stored: "crates.io:cw20-base".to_string(),
+                }
+            );
+
+            // change block time increase 10 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Harvest reward by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // query balance of USER_1 in HALO reward token
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    reward_token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+
+            // USER_1: 20 seconds * 5 HALO
+            assert_eq!(
+                balance.balance,
+                Uint128::from(100_000_000_000_000_000_000u128)
+            );
+        }
+
+        // Create farm contract with a native reward token
+        // Add and fund 2 more phases by ADMIN
+        // Rewrite the farm state to the layout of version 1.1.0 -> query farm info fails
+        // Migrate the farm contract -> farm info with all 3 phases is not changed
+        #[test]
+        fn proper_migrate_from_v1_1_0_with_multiple_phases() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let reward_tokens = vec![PhaseRewardToken {
+                info: TokenInfo::NativeToken {
+                    denom: NATIVE_DENOM_2.to_string(),
+                },
+                whitelist: Addr::unchecked(ADMIN.to_string()),
+            }];
+
+            // create farm with ADMIN as the contract admin to be able to migrate
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: reward_tokens.clone(),
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    Some(ADMIN.to_string()),
+                )
+                .unwrap();
+
+            // Add and fund 2 more phases by ADMIN, each one after the previous phase started
+            for phase_index in 1..3u64 {
+                app.set_block(BlockInfo {
+                    time: Timestamp::from_seconds(current_block_time + (phase_index - 1) * 100),
+                    height: app.block_info().height + 1,
+                    chain_id: app.block_info().chain_id,
+                });
+
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::AddPhase {
+                        new_start_time: current_block_time + phase_index * 100,
+                        new_end_time: current_block_time + (phase_index + 1) * 100,
+                        reward_tokens: reward_tokens.clone(),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::AddRewardBalance {
+                        phase_index,
+                        amount: Uint128::from(1_000u128),
+                    },
+                    &[Coin {
+                        amount: Uint128::from(1_000u128),
+                        denom: NATIVE_DENOM_2.to_string(),
+                    }],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            let query_farm_info = |app: &App| -> StdResult<FarmInfoResponse> {
+                app.wrap()
+                    .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+            };
+            let farm_info = query_farm_info(&app).unwrap();
+            assert_eq!(farm_info.phases_info.len(), 3);
+
+            // Rewrite the farm state as it was deployed with version 1.1.0
+            set_farm_storage(&mut app, &halo_farm_contract_addr, downgrade_to_v1_1_0);
+
+            // The new code can not read the state of version 1.1.0
+            assert!(query_farm_info(&app).is_err());
+
+            // Migrate the farm contract
+            let response = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                halo_farm_contract_addr.clone(),
+                &MigrateMsg {},
+                halo_farm_contract_code_id,
+            );
+
+            assert!(response.is_ok());
+
+            // Farm info is not changed after migration
+            assert_eq!(query_farm_info(&app).unwrap(), farm_info);
+        }
+
+        // Create farm contract with 50 seconds lock tier and x3 multiplier
+        // Deposit 1000 lp token by ADMIN with 50 seconds lock -> boost weight 2000
+        // Deposit 1000 lp token by ADMIN without lock after 10 seconds
+        // Query pending reward after 10 seconds -> ADMIN: 100 NATIVE_2
+        // Rewrite the farm state to the layout of version 1.2.0
+        // Migrate the farm contract -> the boost weight and pending reward are not changed
+        // After 30 seconds the boost weight of ADMIN is removed at the unlock time
+        #[test]
+        fn proper_migrate_from_v1_2_0_with_locks() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 2000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm with ADMIN as the contract admin to be able to migrate
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![LockTier {
+                    duration: 50,
+                    multiplier: Decimal::from_ratio(3u128, 1u128),
+                }],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    Some(ADMIN.to_string()),
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(1_000_000_000u128),
+                },
+                &[Coin {
+                    amount: Uint128::from(1_000_000_000u128),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN with 50 seconds lock
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: Some(50),
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let query_farm_info = |app: &App| -> FarmInfoResponse {
+                app.wrap()
+                    .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+                    .unwrap()
+            };
+            assert_eq!(
+                query_farm_info(&app).boost_weight,
+                Uint128::from(2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT)
+            );
+
+            // change block time increase 10 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute deposit 1000 lp token by ADMIN without lock
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 10 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            let query_pending_reward = |app: &App| -> Uint128 {
+                let pending_reward: PendingRewardResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        halo_farm_contract_addr.clone(),
+                        &FarmQueryMsg::PendingReward {
+                            address: ADMIN.to_string(),
+                        },
+                    )
+                    .unwrap();
+                pending_reward.rewards[0].amount
+            };
+            assert_eq!(query_pending_reward(&app), Uint128::from(100_000_000u128));
+
+            // Rewrite the farm state as it was deployed with version 1.2.0
+            set_farm_storage(&mut app, &halo_farm_contract_addr, downgrade_to_v1_2_0);
+
+            // Migrate the farm contract
+            let response = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                halo_farm_contract_addr.clone(),
+                &MigrateMsg {},
+                halo_farm_contract_code_id,
+            );
+
+            assert!(response.is_ok());
+
+            // The lock of ADMIN is still boosted after migration
+            assert_eq!(
+                query_farm_info(&app).boost_weight,
+                Uint128::from(2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT)
+            );
+            // Pending reward is not changed after migration
+            assert_eq!(query_pending_reward(&app), Uint128::from(100_000_000u128));
+
+            // change block time increase 30 seconds to unlock
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(30),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // The boost weight of ADMIN is removed at the unlock time
+            assert_eq!(query_farm_info(&app).boost_weight, Uint128::zero());
+        }
+    }
+
+    // create farm contract
+    // transfer the ownership of the farm contract in two steps
+    // renounce the ownership of the farm contract
+    mod ownership_operation {
+        use cosmwasm_std::{Addr, BlockInfo};
+        use cw_multi_test::{App, Executor};
+        use cw_utils::Expiration;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            ExecuteMsg as FarmExecuteMsg, InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken,
+            QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{OwnershipResponse, TokenInfo};
+        use crate::tests::env_setup::env::{
+            halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_DENOM_2, USER_1,
+        };
+
+        fn query_ownership(app: &App, farm: &Addr) -> OwnershipResponse {
+            app.wrap()
+                .query_wasm_smart(farm.clone(), &FarmQueryMsg::Ownership {})
+                .unwrap()
+        }
+
+        // Create farm contract owned by ADMIN
+        // Propose USER_1 as new owner by USER_1 -> Unauthorized
+        // Accept ownership by USER_1 without proposal -> NoOwnershipProposal
+        // Propose USER_1 as new owner by ADMIN expiring after 10 blocks
+        // Accept ownership by ADMIN -> Unauthorized
+        // Accept ownership by USER_1 after 11 blocks -> OwnershipProposalExpired
+        // Propose USER_1 again and cancel the proposal by ADMIN
+        // Propose USER_1 again and accept the ownership by USER_1
+        // Add phase by ADMIN -> Unauthorized, add phase by USER_1 -> ok
+        // Renounce ownership by USER_1 -> nobody can remove the phase
+        #[test]
+        fn proper_transfer_and_renounce_ownership() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let reward_tokens = vec![PhaseRewardToken {
+                info: TokenInfo::NativeToken {
+                    denom: NATIVE_DENOM_2.to_string(),
+                },
+                whitelist: Addr::unchecked(ADMIN.to_string()),
+            }];
+
+            // create farm owned by ADMIN
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: reward_tokens.clone(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 110,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            assert_eq!(
+                query_ownership(&app, &halo_farm_contract_addr),
+                OwnershipResponse {
+                    owner: Some(Addr::unchecked(ADMIN)),
+                    pending_owner: None,
+                    pending_expiry: None,
+                }
+            );
+
+            // Execute propose new owner by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::ProposeNewOwner {
+                    owner: USER_1.to_string(),
+                    expiry: None,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Unauthorized {}
+            );
+
+            // Execute accept ownership by USER_1 without proposal
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AcceptOwnership {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoOwnershipProposal {}
+            );
+
+            // Execute propose USER_1 as new owner by ADMIN expiring after 10 blocks
+            let expiry = Expiration::AtHeight(app.block_info().height + 10);
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::ProposeNewOwner {
+                    owner: USER_1.to_string(),
+                    expiry: Some(expiry),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            assert_eq!(
+                query_ownership(&app, &halo_farm_contract_addr),
+                OwnershipResponse {
+                    owner: Some(Addr::unchecked(ADMIN)),
+                    pending_owner: Some(Addr::unchecked(USER_1)),
+                    pending_expiry: Some(expiry),
+                }
+            );
+
+            // Execute accept ownership by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AcceptOwnership {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Unauthorized {}
+            );
+
+            // increase 11 blocks to make the proposal expired
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(55),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute accept ownership by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AcceptOwnership {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::OwnershipProposalExpired {}
+            );
+
+            // Execute propose USER_1 as new owner by ADMIN without expiry
+            let propose_new_owner_msg = FarmExecuteMsg::ProposeNewOwner {
+                owner: USER_1.to_string(),
+                expiry: None,
+            };
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &propose_new_owner_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute cancel ownership proposal by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::CancelOwnershipProposal {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            assert_eq!(
+                query_ownership(&app, &halo_farm_contract_addr),
+                OwnershipResponse {
+                    owner: Some(Addr::unchecked(ADMIN)),
+                    pending_owner: None,
+                    pending_expiry: None,
+                }
+            );
+
+            // Execute cancel ownership proposal by ADMIN again
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::CancelOwnershipProposal {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoOwnershipProposal {}
+            );
+
+            // Execute propose USER_1 as new owner by ADMIN again
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &propose_new_owner_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute accept ownership by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AcceptOwnership {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            assert_eq!(
+                query_ownership(&app, &halo_farm_contract_addr),
+                OwnershipResponse {
+                    owner: Some(Addr::unchecked(USER_1)),
+                    pending_owner: None,
+                    pending_expiry: None,
+                }
+            );
+
+            // add new phase after the current phase
+            let add_phase_msg = FarmExecuteMsg::AddPhase {
+                new_start_time: current_block_time + 110,
+                new_end_time: current_block_time + 210,
+                reward_tokens,
+            };
+
+            // Execute add phase by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &add_phase_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Unauthorized {}
+            );
+
+            // Execute add phase by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &add_phase_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute renounce ownership by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::RenounceOwnership {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            assert_eq!(
+                query_ownership(&app, &halo_farm_contract_addr),
+                OwnershipResponse {
+                    owner: None,
+                    pending_owner: None,
+                    pending_expiry: None,
+                }
+            );
+
+            // Execute remove phase by USER_1 after renouncing the ownership
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::RemovePhase { phase_index: 1 },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Unauthorized {}
+            );
+        }
+    }
+
+    // create farm contract
+    // deposit lp token to the farm contract by many stakers
+    // query all stakers with pagination
+    mod query_operation {
+        use cosmwasm_std::{
+            to_binary, Addr, BlockInfo, Coin, Decimal, Decimal256, StdResult, Uint128,
+        };
+        use cw20::Cw20ExecuteMsg;
+        use cw_multi_test::{App, Executor};
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{
+            AllStakersResponse, LockTier, PhaseResponse, PhaseStatus, PhasesResponse,
+            RewardEmission, RewardRateResponse, RewardTokenAsset, RewardTokenRate, StakerResponse,
+            TokenInfo,
+        };
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Create farm contract with 1000 NATIVE_2 reward balance -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token to the farm contract by 35 stakers
+        // Query all stakers after 10 seconds -> each staker has 100 / 35 NATIVE_2 pending reward
+        // Query without limit -> 10 stakers
+        // Query with limit 100 -> 30 stakers
+        // Query after the 30th staker -> 5 stakers
+        #[test]
+        fn proper_query_all_stakers() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let native_token_info = TokenInfo::NativeToken {
+                denom: NATIVE_DENOM_2.to_string(),
+            };
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 1 second to make phase active
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // 35 stakers ordered by address
+            let stakers: Vec<String> = (0..35).map(|i| format!("aura1staker{i:02}")).collect();
+
+            for staker in stakers.iter() {
+                // Mint 1000 HALO LP tokens to the staker
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: staker.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+
+                // Execute deposit by the staker
+                let response = app.execute_contract(
+                    Addr::unchecked(staker.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Send {
+                        contract: halo_farm_contract_addr.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                        msg: to_binary(&FarmCw20HookMsg::Deposit {
+                            lock_duration: None,
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // change block time increase 10 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query all stakers without limit
+            let all_stakers: AllStakersResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::AllStakers {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            // It should be the first 10 stakers
+            // with 100 NATIVE_2 shared by 35 stakers as pending reward
+            assert_eq!(all_stakers.time_query, app.block_info().time.seconds());
+            assert_eq!(
+                all_stakers.stakers,
+                stakers[..10]
+                    .iter()
+                    .map(|staker| StakerResponse {
+                        address: Addr::unchecked(staker),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                        joined_phase: 0u64,
+                        pending_rewards: vec![RewardTokenAsset {
+                            info: native_token_info.clone(),
+                            amount: Uint128::from(2_857_142u128),
+                        }],
+                    })
+                    .collect::<Vec<StakerResponse>>()
+            );
+
+            // Query all stakers with limit greater than the max limit
+            let all_stakers: AllStakersResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::AllStakers {
+                        start_after: None,
+                        limit: Some(100),
+                    },
+                )
+                .unwrap();
+
+            // It should be limited to 30 stakers
+            assert_eq!(
+                all_stakers
+                    .stakers
+                    .iter()
+                    .map(|staker| staker.address.to_string())
+                    .collect::<Vec<String>>(),
+                stakers[..30].to_vec()
+            );
+
+            // Query all stakers after the 30th staker
+            let all_stakers: AllStakersResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr,
+                    &FarmQueryMsg::AllStakers {
+                        start_after: Some(stakers[29].clone()),
+                        limit: Some(100),
+                    },
+                )
+                .unwrap();
+
+            // It should be the last 5 stakers
+            assert_eq!(
+                all_stakers
+                    .stakers
+                    .iter()
+                    .map(|staker| staker.address.to_string())
+                    .collect::<Vec<String>>(),
+                stakers[30..].to_vec()
+            );
+        }
+
+        fn query_phase(app: &App, farm: &Addr, phase_index: u64) -> StdResult<PhaseResponse> {
+            app.wrap()
+                .query_wasm_smart(farm.clone(), &FarmQueryMsg::Phase { phase_index })
+        }
+
+        fn query_current_phase(app: &App, farm: &Addr) -> PhaseResponse {
+            app.wrap()
+                .query_wasm_smart(farm.clone(), &FarmQueryMsg::CurrentPhase {})
+                .unwrap()
+        }
+
+        // Create farm contract with phase 0 starting after 10 seconds for 100 seconds
+        // Query phase 0 -> pending with zero emission rate
+        // Add 1000 NATIVE_2 reward balance -> funded with 10 NATIVE_2 per second
+        // After 30 seconds query current phase -> phase 0 is active with 80 seconds remaining
+        // Add phase 1 for 100 seconds and 500 NATIVE_2 reward balance -> funded with 5 NATIVE_2 per second
+        // Query phases -> 2 phases, query phases after phase 0 -> phase 1, query phase 2 -> error
+        // After 150 seconds phase 0 is ended and phase 1 is active with 70 seconds remaining
+        // After 300 seconds query current phase -> phase 1 is ended
+        #[test]
+        fn proper_query_phases() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let native_token_info = TokenInfo::NativeToken {
+                denom: NATIVE_DENOM_2.to_string(),
+            };
+            let reward_tokens = vec![PhaseRewardToken {
+                info: native_token_info.clone(),
+                whitelist: Addr::unchecked(ADMIN.to_string()),
+            }];
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &FarmInstantiateMsg {
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                        },
+                        reward_tokens: reward_tokens.clone(),
+                        start_time: current_block_time + 10,
+                        end_time: current_block_time + 110,
+                        phases_limit_per_user: None,
+                        max_total_staked: None,
+                        lock_tiers: vec![],
+                        farm_owner: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Phase 0 is pending without reward balance
+            let phase = query_phase(&app, &halo_farm_contract_addr, 0).unwrap();
+            assert_eq!(phase.phase_index, 0);
+            assert_eq!(phase.status, PhaseStatus::Pending);
+            assert_eq!(phase.time_remaining, 100);
+            assert_eq!(
+                phase.emission_rates,
+                vec![RewardEmission {
+                    info: native_token_info.clone(),
+                    emission_rate: Decimal256::zero(),
+                }]
+            );
+
+            // Add reward balance to the phase by ADMIN
+            let add_reward_balance = |app: &mut App, phase_index: u64, amount: u128| {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::AddRewardBalance {
+                        phase_index,
+                        amount: Uint128::from(amount),
+                    },
+                    &[Coin {
+                        amount: Uint128::from(amount),
+                        denom: NATIVE_DENOM_2.to_string(),
+                    }],
+                );
+
+                assert!(response.is_ok());
+            };
+
+            add_reward_balance(&mut app, 0, ADD_1000_NATIVE_BALANCE_2);
+
+            // Phase 0 is funded with 10 NATIVE_2 per second
+            let phase = query_phase(&app, &halo_farm_contract_addr, 0).unwrap();
+            assert_eq!(phase.status, PhaseStatus::Funded);
+            assert_eq!(
+                phase.emission_rates[0].emission_rate,
+                Decimal256::from_ratio(10_000_000u128, 1u128)
+            );
+
+            // increase 30 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(30),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Phase 0 is active with 80 seconds remaining
+            let phase = query_current_phase(&app, &halo_farm_contract_addr);
+            assert_eq!(phase.phase_index, 0);
+            assert_eq!(phase.status, PhaseStatus::Active);
+            assert_eq!(phase.time_remaining, 80);
+
+            // Execute add phase 1 by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddPhase {
+                    new_start_time: current_block_time + 120,
+                    new_end_time: current_block_time + 220,
+                    reward_tokens,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            add_reward_balance(&mut app, 1, ADD_1000_NATIVE_BALANCE_2 / 2);
+
+            // Query all phases
+            let phases: PhasesResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::Phases {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                phases
+                    .phases
+                    .iter()
+                    .map(|phase| (phase.phase_index, phase.status.clone()))
+                    .collect::<Vec<_>>(),
+                vec![(0, PhaseStatus::Active), (1, PhaseStatus::Funded)]
+            );
+            // Phase 1 is funded with 5 NATIVE_2 per second
+            assert_eq!(
+                phases.phases[1].emission_rates[0].emission_rate,
+                Decimal256::from_ratio(5_000_000u128, 1u128)
+            );
+            assert_eq!(phases.phases[1].time_remaining, 100);
+
+            // Query phases after phase 0
+            let phases: PhasesResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::Phases {
+                        start_after: Some(0),
+                        limit: Some(10),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(phases.phases.len(), 1);
+            assert_eq!(phases.phases[0].phase_index, 1);
+
+            // Phase 2 does not exist
+            assert!(query_phase(&app, &halo_farm_contract_addr, 2).is_err());
+
+            // change block time to 150 seconds after the creation
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(120),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Phase 0 is ended and phase 1 is active with 70 seconds remaining
+            let phase = query_phase(&app, &halo_farm_contract_addr, 0).unwrap();
+            assert_eq!(phase.status, PhaseStatus::Ended);
+            assert_eq!(phase.time_remaining, 0);
+
+            let phase = query_current_phase(&app, &halo_farm_contract_addr);
+            assert_eq!(phase.phase_index, 1);
+            assert_eq!(phase.status, PhaseStatus::Active);
+            assert_eq!(phase.time_remaining, 70);
+
+            // change block time to 300 seconds after the creation
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(150),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Phase 1 is ended
+            let phase = query_current_phase(&app, &halo_farm_contract_addr);
+            assert_eq!(phase.phase_index, 1);
+            assert_eq!(phase.status, PhaseStatus::Ended);
+            assert_eq!(phase.time_remaining, 0);
+        }
+
+        fn query_reward_rate(
+            app: &App,
+            farm: &Addr,
+            deposit_amount: Option<u128>,
+            duration: Option<u64>,
+            lock_duration: Option<u64>,
+        ) -> StdResult<RewardRateResponse> {
+            app.wrap().query_wasm_smart(
+                farm.clone(),
+                &FarmQueryMsg::RewardRate {
+                    deposit_amount: deposit_amount.map(Uint128::from),
+                    duration,
+                    lock_duration,
+                },
+            )
+        }
+
+        // Create farm contract with phase 0 starting after 10 seconds for 100 seconds
+        // and 40 seconds lock tier with x3 multiplier
+        // Add 1000 NATIVE_2 reward balance -> 10 NATIVE_2 per second after the start time
+        // Query reward rate of 1000 lp token for 50 seconds -> not emitting yet,
+        // 400 NATIVE_2 projected for the 40 seconds after the start time
+        // Deposit 1000 lp token by ADMIN
+        // After 30 seconds query reward rate -> 10 NATIVE_2 per second,
+        // 0.01 NATIVE_2 per lp token per second
+        // Query reward rate of 1000 lp token for 100 seconds -> half of the 80 seconds left,
+        // 400 NATIVE_2 projected
+        // Query reward rate of 1000 lp token for 100 seconds with 40 seconds lock
+        // -> 3/4 of the 40 seconds locked and half of the 40 seconds after, 500 NATIVE_2 projected
+        // Query reward rate with 10 seconds lock -> InvalidLockDuration
+        // Query reward rate without deposit -> nothing projected
+        // After 120 seconds query reward rate -> phase 0 is ended, nothing emitted
+        #[test]
+        fn proper_query_reward_rate() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let native_token_info = TokenInfo::NativeToken {
+                denom: NATIVE_DENOM_2.to_string(),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &FarmInstantiateMsg {
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                        },
+                        reward_tokens: vec![PhaseRewardToken {
+                            info: native_token_info.clone(),
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                        }],
+                        start_time: current_block_time + 10,
+                        end_time: current_block_time + 110,
+                        phases_limit_per_user: None,
+                        max_total_staked: None,
+                        lock_tiers: vec![LockTier {
+                            duration: 40,
+                            multiplier: Decimal::from_ratio(3u128, 1u128),
+                        }],
+                        farm_owner: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Phase 0 is not emitting before the start time,
+            // the deposit earns all the reward of the 40 seconds after the start time
+            let reward_rate = query_reward_rate(
+                &app,
+                &halo_farm_contract_addr,
+                Some(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                Some(50),
+                None,
+            )
+            .unwrap();
+            assert_eq!(
+                reward_rate,
+                RewardRateResponse {
+                    phase_index: 0,
+                    status: PhaseStatus::Funded,
+                    total_weight: Uint128::zero(),
+                    rewards: vec![RewardTokenRate {
+                        info: native_token_info.clone(),
+                        emission_rate: Decimal256::zero(),
+                        reward_per_share_rate: Decimal256::zero(),
+                        projected_reward: Uint128::from(400_000_000u128),
+                    }],
+                    time_query: current_block_time,
+                }
+            );
+
+            // Mint 1000 HALO LP tokens to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // deposit lp token to the farm contract
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
+            };
+
+            // Execute deposit by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 30 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(30),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Phase 0 emits 10 NATIVE_2 per second to 1000 lp token,
+            // the deposit earns half of the reward of the 80 seconds left
+            let reward_rate = query_reward_rate(
+                &app,
+                &halo_farm_contract_addr,
+                Some(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                Some(100),
+                None,
+            )
+            .unwrap();
+            assert_eq!(
+                reward_rate,
+                RewardRateResponse {
+                    phase_index: 0,
+                    status: PhaseStatus::Active,
+                    total_weight: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    rewards: vec![RewardTokenRate {
+                        info: native_token_info.clone(),
+                        emission_rate: Decimal256::from_ratio(10_000_000u128, 1u128),
+                        // 0.01 NATIVE_2 per lp token per second
+                        reward_per_share_rate: Decimal256::percent(1),
+                        projected_reward: Uint128::from(400_000_000u128),
+                    }],
+                    time_query: current_block_time + 30,
+                }
+            );
+
+            // The locked deposit is boosted until the unlock time only
+            let reward_rate = query_reward_rate(
+                &app,
+                &halo_farm_contract_addr,
+                Some(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                Some(100),
+                Some(40),
+            )
+            .unwrap();
+            assert_eq!(
+                reward_rate.rewards[0].projected_reward,
+                Uint128::from(500_000_000u128)
+            );
+
+            // Only the duration of a lock tier can be projected
+            let error = query_reward_rate(
+                &app,
+                &halo_farm_contract_addr,
+                Some(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                Some(100),
+                Some(10),
+            )
+            .unwrap_err();
+            assert!(error
+                .to_string()
+                .contains(&ContractError::InvalidLockDuration { duration: 10 }.to_string()));
+
+            // Nothing is projected without deposit amount
+            let reward_rate =
+                query_reward_rate(&app, &halo_farm_contract_addr, None, None, None).unwrap();
+            assert_eq!(reward_rate.rewards[0].projected_reward, Uint128::zero());
+            assert_eq!(
+                reward_rate.rewards[0].emission_rate,
+                Decimal256::from_ratio(10_000_000u128, 1u128)
+            );
+
+            // change block time to 120 seconds after the creation
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(90),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Phase 0 is ended, nothing is emitted
+            let reward_rate = query_reward_rate(
+                &app,
+                &halo_farm_contract_addr,
+                Some(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                Some(100),
+                None,
+            )
+            .unwrap();
+            assert_eq!(reward_rate.status, PhaseStatus::Ended);
+            assert_eq!(
+                reward_rate.rewards,
+                vec![RewardTokenRate {
+                    info: native_token_info,
+                    emission_rate: Decimal256::zero(),
+                    reward_per_share_rate: Decimal256::zero(),
+                    projected_reward: Uint128::zero(),
+                }]
+            );
+        }
+
+        // Create farm contract with 1000 NATIVE_2 reward balance for 100 seconds
+        // Deposit 1000 lp token by ADMIN
+        // After 30 seconds extend the phase to 160 seconds
+        // -> the 700 NATIVE_2 left are emitted over the 130 seconds left
+        // Query reward rate of 1000 lp token for 60 seconds
+        // -> 7 / 1300 NATIVE_2 per lp token per second, 161.538461 NATIVE_2 projected
+        // Deposit 1000 lp token by USER_1, harvest after 60 seconds -> the projected reward
+        #[test]
+        fn proper_query_reward_rate_after_extend_phase() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN and USER_1
+            for recipient in [ADMIN, USER_1] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &FarmInstantiateMsg {
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                        },
+                        reward_tokens: vec![PhaseRewardToken {
+                            info: TokenInfo::NativeToken {
+                                denom: NATIVE_DENOM_2.to_string(),
+                            },
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                        }],
+                        start_time: current_block_time,
+                        end_time: current_block_time + 100,
+                        phases_limit_per_user: None,
+                        max_total_staked: None,
+                        lock_tiers: vec![],
+                        farm_owner: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // deposit lp token to the farm contract
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
+            };
+
+            // Execute deposit by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 30 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(30),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute extend phase 0 to 160 seconds by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::ExtendPhase {
+                    new_end_time: current_block_time + 160,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // The 700 NATIVE_2 left are emitted over the 130 seconds left,
+            // the deposit earns half of the emission of the next 60 seconds
+            let reward_rate = query_reward_rate(
+                &app,
+                &halo_farm_contract_addr,
+                Some(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                Some(60),
+                None,
+            )
+            .unwrap();
+            assert_eq!(reward_rate.status, PhaseStatus::Active);
+            assert_eq!(
+                reward_rate.rewards[0].reward_per_share_rate,
+                Decimal256::from_ratio(7u128, 1300u128)
+            );
+            assert_eq!(
+                reward_rate.rewards[0].projected_reward,
+                Uint128::from(161_538_461u128)
+            );
+
+            // Execute deposit by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 60 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(60),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute harvest by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 receives the projected reward
+            let balance = app.wrap().query_balance(USER_1, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(balance.amount, reward_rate.rewards[0].projected_reward);
+        }
+    }
+
+    // create farm contract
+    // deposit lp token to the farm contract
+    // emergency withdraw lp token from the farm contract
+    mod emergency_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Event, Uint128};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg};
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{PendingRewardResponse, TokenInfo};
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Create farm contract with 1000 NATIVE_2 reward balance -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token to the farm contract by ADMIN and USER_1
+        // Emergency withdraw by USER_1 after 10 seconds -> 1000 lp token back, no reward
+        // Emergency withdraw by USER_1 again -> NotStaker
+        // Query pending reward of ADMIN after 10 seconds -> 100 + 100 NATIVE_2,
+        // the reward is not settled by the emergency withdraw
+        #[test]
+        fn proper_emergency_withdraw() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 1 second to make phase active
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            for staker in [ADMIN, USER_1] {
+                // Mint 1000 HALO LP tokens to the staker
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: staker.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+
+                // Execute deposit by the staker
+                let response = app.execute_contract(
+                    Addr::unchecked(staker.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Send {
+                        contract: halo_farm_contract_addr.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                        msg: to_binary(&FarmCw20HookMsg::Deposit {
+                            lock_duration: None,
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // change block time increase 10 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute emergency withdraw by USER_1
+            let response = app
+                .execute_contract(
+                    Addr::unchecked(USER_1.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::EmergencyWithdraw {},
+                    &[],
+                )
+                .unwrap();
+
+            // It should emit the emergency withdraw event
+            assert!(response.has_event(
+                &Event::new("wasm-emergency_withdraw")
+                    .add_attribute("staker", USER_1)
+                    .add_attribute("amount", MOCK_1000_HALO_LP_TOKEN_AMOUNT.to_string())
+            ));
+
+            // query balance of USER_1 in lp token
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    lp_token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                balance.balance,
+                Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT)
+            );
+
+            // USER_1 does not receive any reward
+            let balance = app.wrap().query_balance(USER_1, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(balance.amount, Uint128::zero());
+
+            // Total staked is decreased by the staked amount of USER_1
+            let total_staked: Uint128 = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::TotalStaked {},
+                )
+                .unwrap();
+
+            assert_eq!(total_staked, Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT));
+
+            // Execute emergency withdraw by USER_1 again
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::EmergencyWithdraw {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NotStaker {
+                    address: Addr::unchecked(USER_1)
+                }
+            );
+
+            // change block time increase 10 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query pending reward of ADMIN
+            let pending_reward: PendingRewardResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr,
+                    &FarmQueryMsg::PendingReward {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            // ADMIN: 20 seconds * 10 NATIVE_2, the reward of the first 10 seconds
+            // is distributed with the total weight after the emergency withdraw
+            assert_eq!(
+                pending_reward.rewards[0].amount,
+                Uint128::from(200_000_000u128)
+            );
+        }
+    }
+
+    // create farm contract
+    // pause and unpause the farm contract
+    // only withdraw and emergency withdraw are allowed while paused
+    mod pause_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Uint128};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg};
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{
+            Config, PendingRewardResponse, PhaseSettlementResponse, StakerInfoResponse, TokenInfo,
+        };
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_BALANCE_2,
+                NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Create farm contract starting after 10 seconds
+        // Pause by USER_1 -> Unauthorized
+        // Pause by ADMIN, add reward balance -> Paused
+        // Renounce ownership by ADMIN while paused -> RenounceWhilePaused
+        // Unpause by ADMIN, add 1000 NATIVE_2 reward balance -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by ADMIN
+        // Pause by ADMIN after 20 seconds, deposit and harvest -> Paused
+        // Withdraw 500 lp token by ADMIN -> no reward while paused
+        // Emergency withdraw by ADMIN -> 500 lp token, the unpaid reward is undistributed
+        #[test]
+        fn proper_pause_and_unpause() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 2000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 110,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Query config
+            let config: Config = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Config {})
+                .unwrap();
+
+            assert_eq!(
+                config,
+                Config {
+                    farm_owner: Some(Addr::unchecked(ADMIN)),
+                    paused: false,
+                    max_total_staked: None,
+                    lock_tiers: vec![],
+                }
+            );
+
+            // Execute pause by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Pause {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Unauthorized {}
+            );
+
+            // Execute pause by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Pause {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query config
+            let config: Config = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Config {})
+                .unwrap();
+
+            assert!(config.paused);
+
+            // add 1000 NATIVE_2 reward balance to farm contract
+            let add_reward_balance_msg = FarmExecuteMsg::AddRewardBalance {
+                phase_index: 0u64,
+                amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+            };
+            let funds = [Coin {
+                amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                denom: NATIVE_DENOM_2.to_string(),
+            }];
+
+            // Execute add reward balance by ADMIN while paused
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &add_reward_balance_msg,
+                &funds,
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Paused {}
+            );
+
+            // Execute renounce ownership by ADMIN while paused
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::RenounceOwnership {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::RenounceWhilePaused {}
+            );
+
+            // Execute unpause by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Unpause {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute add reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &add_reward_balance_msg,
+                &funds,
+            );
+
+            assert!(response.is_ok());
+
+            // Deposit lp token to the farm contract to execute deposit msg
+            let deposit_msg = Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
+            };
+
+            // Execute deposit by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 20 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(20),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute pause by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Pause {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit by ADMIN while paused
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Paused {}
+            );
+
+            // Execute harvest by ADMIN while paused
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Paused {}
+            );
+
+            // Execute withdraw 500 lp token by ADMIN while paused
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives no reward while paused
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(
+                balance.amount,
+                Uint128::from(NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2)
+            );
+
+            // Execute emergency withdraw by ADMIN while paused
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::EmergencyWithdraw {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN gets all lp token back
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    lp_token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                balance.balance,
+                Uint128::from(2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT)
+            );
+
+            // The unpaid reward of 10 seconds * 10 NATIVE_2 is undistributed
+            let settlement: PhaseSettlementResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr,
+                    &FarmQueryMsg::PhaseSettlement { phase_index: 0 },
+                )
+                .unwrap();
+
+            assert_eq!(settlement.rewards[0].distributed_reward, Uint128::zero());
+            assert_eq!(
+                settlement.rewards[0].undistributed_reward,
+                Uint128::from(100_000_000u128)
+            );
+        }
+
+        // Create farm contract starting after 10 seconds with 1000 NATIVE_2 reward balance
+        // -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by ADMIN
+        // Pause by ADMIN after 20 seconds
+        // Withdraw 500 lp token by ADMIN -> 500 lp token back, no reward, 100 NATIVE_2 pending
+        // Withdraw 500 lp token by ADMIN after 10 seconds -> no reward, 200 NATIVE_2 pending
+        // Unpause by ADMIN, harvest by ADMIN -> 200 NATIVE_2 reward, ADMIN is not staker anymore
+        #[test]
+        fn proper_withdraw_while_paused() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &FarmInstantiateMsg {
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                        },
+                        reward_tokens: vec![PhaseRewardToken {
+                            info: TokenInfo::NativeToken {
+                                denom: NATIVE_DENOM_2.to_string(),
+                            },
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                        }],
+                        start_time: current_block_time + 10,
+                        end_time: current_block_time + 110,
+                        phases_limit_per_user: None,
+                        max_total_staked: None,
+                        lock_tiers: vec![],
+                        farm_owner: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 20 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(20),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute pause by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Pause {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let withdraw_msg = FarmExecuteMsg::Withdraw {
+                amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                recipient: None,
+            };
+
+            let query_pending_reward = |app: &cw_multi_test::App| -> Uint128 {
+                let pending_reward: PendingRewardResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        halo_farm_contract_addr.clone(),
+                        &FarmQueryMsg::PendingReward {
+                            address: ADMIN.to_string(),
+                        },
+                    )
+                    .unwrap();
+                pending_reward.rewards[0].amount
+            };
+
+            // Execute withdraw 500 lp token by ADMIN while paused
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &withdraw_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives 500 lp token without reward,
+            // the reward of 10 seconds * 10 NATIVE_2 is still pending
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    lp_token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                balance.balance,
+                Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2)
+            );
+            assert_eq!(
+                app.wrap()
+                    .query_balance(ADMIN, NATIVE_DENOM_2)
+                    .unwrap()
+                    .amount,
+                Uint128::from(NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2)
+            );
+            assert_eq!(query_pending_reward(&app), Uint128::from(100_000_000u128));
+
+            // change block time increase 10 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute withdraw the remaining 500 lp token by ADMIN while paused
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &withdraw_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // The staker info is kept for the unpaid reward of 20 seconds * 10 NATIVE_2
+            let staker_info: StakerInfoResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::StakerInfo {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(staker_info.amount, Uint128::zero());
+            assert_eq!(query_pending_reward(&app), Uint128::from(200_000_000u128));
+
+            // Execute unpause by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Unpause {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute harvest by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives the unpaid reward
+            assert_eq!(
+                app.wrap()
+                    .query_balance(ADMIN, NATIVE_DENOM_2)
+                    .unwrap()
+                    .amount,
+                Uint128::from(NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2 + 200_000_000u128)
+            );
+
+            // ADMIN is not staker anymore
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NotStaker {
+                    address: Addr::unchecked(ADMIN)
+                }
+            );
+        }
+    }
+
+    // create farm contract
+    // deposit and withdraw lp token leaving periods without staked token
+    // reclaim the undistributed reward after the phase ends
+    mod reclaim_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Uint128};
+        use cw20::Cw20ExecuteMsg;
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{FarmInfoResponse, TokenInfo};
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_BALANCE_2,
+                NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Create farm contract with 1000 NATIVE_2 reward balance -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by ADMIN after 10 seconds -> 100 NATIVE_2 undistributed
+        // Withdraw 1000 lp token by ADMIN after 40 seconds -> 400 NATIVE_2 reward
+        // Reclaim before the phase ends -> PhaseNotEnded
+        // Reclaim after the phase ends by USER_1 -> NotWhitelisted
+        // Reclaim after the phase ends by ADMIN -> 100 + 500 NATIVE_2
+        // Reclaim again -> NoUndistributedReward
+        // Reclaim from phase 1 -> PhaseNotFound
+        #[test]
+        fn proper_reclaim_undistributed() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 10 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute deposit 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query farm info
+            let farm_info: FarmInfoResponse = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+                .unwrap();
+
+            // 10 seconds * 10 NATIVE_2 is undistributed
+            assert_eq!(
+                farm_info.phases_info[0].reward_tokens[0].undistributed_reward,
+                Uint128::from(100_000_000u128)
+            );
+
+            // change block time increase 40 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(40),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute withdraw 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute reclaim undistributed by ADMIN before the phase ends
+            let reclaim_msg = FarmExecuteMsg::ReclaimUndistributed { phase_index: 0 };
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &reclaim_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::PhaseNotEnded {
+                    phase_index: 0,
+                    end_time: current_block_time + 100,
+                }
+            );
+
+            // change block time increase 60 seconds to end the phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(60),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute reclaim undistributed by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &reclaim_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NotWhitelisted {
+                    sender: Addr::unchecked(USER_1)
+                }
+            );
+
+            // Execute reclaim undistributed by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &reclaim_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives 400 NATIVE_2 as reward and 600 NATIVE_2 as undistributed reward
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(NATIVE_BALANCE_2));
+
+            // Query farm info
+            let farm_info: FarmInfoResponse = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+                .unwrap();
+
+            assert_eq!(
+                farm_info.phases_info[0].reward_tokens[0].undistributed_reward,
+                Uint128::zero()
+            );
+
+            // Execute reclaim undistributed by ADMIN again
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &reclaim_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoUndistributedReward { phase_index: 0 }
+            );
+
+            // Execute reclaim undistributed of phase 1 by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::ReclaimUndistributed { phase_index: 1 },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::PhaseNotFound { phase_index: 1 }
+            );
+        }
+    }
+
+    mod settlement_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Uint128};
+        use cw20::Cw20ExecuteMsg;
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{PhaseSettlementResponse, TokenInfo};
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_BALANCE_2,
+                NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Create farm contract with 1000 NATIVE_2 reward balance
+        // Deposit 1000 lp token by ADMIN and 2000 lp token by USER_1 at start time
+        // Remove reward balance before the phase ends -> PhaseNotEnded
+        // After the phase ends, 1000 NATIVE_2 is owed to the stakers -> NoSurplusReward
+        // Withdraw by ADMIN -> 333.333333 NATIVE_2 reward, still no surplus
+        // Withdraw by USER_1 -> 666.666666 NATIVE_2 reward, 0.000001 NATIVE_2 surplus
+        // Remove reward balance by USER_1 -> NotWhitelisted
+        // Remove reward balance by ADMIN -> 0.000001 NATIVE_2
+        // Remove reward balance again -> NoSurplusReward
+        #[test]
+        fn proper_remove_reward_balance() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN and 2000 HALO LP tokens to USER_1
+            for (recipient, amount) in [
+                (ADMIN, MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                (USER_1, 2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+            ] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(amount),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN and 2000 lp token by USER_1
+            for (staker, amount) in [
+                (ADMIN, MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                (USER_1, 2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+            ] {
+                let response = app.execute_contract(
+                    Addr::unchecked(staker.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Send {
+                        contract: halo_farm_contract_addr.to_string(),
+                        amount: Uint128::from(amount),
+                        msg: to_binary(&FarmCw20HookMsg::Deposit {
+                            lock_duration: None,
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // Execute remove reward balance by ADMIN before the phase ends
+            let remove_reward_balance_msg = FarmExecuteMsg::RemoveRewardBalance { phase_index: 0 };
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &remove_reward_balance_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::PhaseNotEnded {
+                    phase_index: 0,
+                    end_time: current_block_time + 100,
+                }
+            );
+
+            // change block time increase 100 seconds to end the phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query settlement of phase 0
+            let settlement: PhaseSettlementResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::PhaseSettlement { phase_index: 0 },
+                )
+                .unwrap();
+
+            // All reward balance is distributed and owed to the stakers
+            assert!(settlement.ended);
+            assert_eq!(
+                settlement.rewards[0].distributed_reward,
+                Uint128::from(ADD_1000_NATIVE_BALANCE_2)
+            );
+            assert_eq!(
+                settlement.rewards[0].owed_reward,
+                Uint128::from(ADD_1000_NATIVE_BALANCE_2)
+            );
+            assert_eq!(settlement.rewards[0].surplus_reward, Uint128::zero());
+
+            // Execute remove reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &remove_reward_balance_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoSurplusReward { phase_index: 0 }
+            );
+
+            // Execute withdraw all lp token by ADMIN and USER_1
+            for (staker, amount) in [
+                (ADMIN, MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                (USER_1, 2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+            ] {
+                let response = app.execute_contract(
+                    Addr::unchecked(staker.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::Withdraw {
+                        amount: Uint128::from(amount),
+                        recipient: None,
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+
+                // The reward still owed to USER_1 is not a surplus
+                if staker == ADMIN {
+                    let settlement: PhaseSettlementResponse = app
+                        .wrap()
+                        .query_wasm_smart(
+                            halo_farm_contract_addr.clone(),
+                            &FarmQueryMsg::PhaseSettlement { phase_index: 0 },
+                        )
+                        .unwrap();
+
+                    assert_eq!(
+                        settlement.rewards[0].claimed_reward,
+                        Uint128::from(333_333_333u128)
+                    );
+                    assert_eq!(
+                        settlement.rewards[0].owed_reward,
+                        Uint128::from(666_666_667u128)
+                    );
+                    assert_eq!(settlement.rewards[0].surplus_reward, Uint128::zero());
+                }
+            }
+
+            // USER_1 receives 666.666666 NATIVE_2 as reward
+            let balance = app.wrap().query_balance(USER_1, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(666_666_666u128));
+
+            // Query settlement of phase 0
+            let settlement: PhaseSettlementResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::PhaseSettlement { phase_index: 0 },
+                )
+                .unwrap();
+
+            // The rounding dust is the surplus after all stakers have withdrawn
+            assert_eq!(
+                settlement.rewards[0].claimed_reward,
+                Uint128::from(999_999_999u128)
+            );
+            assert_eq!(settlement.rewards[0].owed_reward, Uint128::zero());
+            assert_eq!(settlement.rewards[0].surplus_reward, Uint128::one());
+
+            // Execute remove reward balance by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &remove_reward_balance_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NotWhitelisted {
+                    sender: Addr::unchecked(USER_1)
+                }
+            );
+
+            // Execute remove reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &remove_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives 333.333333 NATIVE_2 as reward and 0.000001 NATIVE_2 as surplus
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(
+                balance.amount,
+                Uint128::from(NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2 + 333_333_334u128)
+            );
+
+            // Execute remove reward balance by ADMIN again
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &remove_reward_balance_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoSurplusReward { phase_index: 0 }
+            );
+        }
+
+        // Create farm contract with 1000 NATIVE_2 reward balance for 100 seconds
+        // Deposit 1000 lp token by USER_1 at start time
+        // After 20 seconds, pause and withdraw all lp token by USER_1
+        // -> 200 NATIVE_2 reward is unpaid
+        // Unpause after the phase ends, the unpaid reward is owed -> NoSurplusReward
+        // Harvest by USER_1 -> 200 NATIVE_2 reward
+        // USER_1 has left the farm and the remaining reward is undistributed -> NoSurplusReward
+        #[test]
+        fn proper_remove_reward_balance_with_unpaid_reward() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: USER_1.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &FarmInstantiateMsg {
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                        },
+                        reward_tokens: vec![PhaseRewardToken {
+                            info: TokenInfo::NativeToken {
+                                denom: NATIVE_DENOM_2.to_string(),
+                            },
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                        }],
+                        start_time: current_block_time,
+                        end_time: current_block_time + 100,
+                        phases_limit_per_user: None,
+                        max_total_staked: None,
+                        lock_tiers: vec![],
+                        farm_owner: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 20 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(20),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute pause by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Pause {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute withdraw all lp token by USER_1 while paused
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 80 seconds to end the phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(80),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute unpause by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Unpause {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query settlement of phase 0
+            let settlement: PhaseSettlementResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::PhaseSettlement { phase_index: 0 },
+                )
+                .unwrap();
+
+            // The unpaid reward of USER_1 is still owed without staked token in the farm
+            assert!(settlement.ended);
+            assert_eq!(
+                settlement.rewards[0].owed_reward,
+                Uint128::from(200_000_000u128)
+            );
+            assert_eq!(
+                settlement.rewards[0].undistributed_reward,
+                Uint128::from(800_000_000u128)
+            );
+            assert_eq!(settlement.rewards[0].surplus_reward, Uint128::zero());
+
+            // Execute remove reward balance by ADMIN
+            let remove_reward_balance_msg = FarmExecuteMsg::RemoveRewardBalance { phase_index: 0 };
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &remove_reward_balance_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoSurplusReward { phase_index: 0 }
+            );
+
+            // Execute harvest by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 receives 200 NATIVE_2 as reward
+            let balance = app.wrap().query_balance(USER_1, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(200_000_000u128));
+
+            // Execute remove reward balance by ADMIN again
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &remove_reward_balance_msg,
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoSurplusReward { phase_index: 0 }
+            );
+        }
+    }
+
+    mod rollover_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Decimal256, Uint128};
+        use cw20::Cw20ExecuteMsg;
+        use cw_multi_test::{App, Executor};
+
+        use crate::error::ContractError;
+        use crate::formulas::ACCRUED_TOKEN_PER_SHARE_SCALE;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{FarmInfoResponse, PendingRewardResponse, TokenInfo};
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_BALANCE_2,
+                NATIVE_DENOM_2,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Create farm contract with phase 0 from start time to start time + 100
+        // and phase 1 from start time + 110 to start time + 200,
+        // add 1000 NATIVE_2 reward balance to phase 0 and to phase 1 if it is funded
+        fn create_farm_with_two_phases(
+            app: &mut App,
+            lp_token_contract: &str,
+            funded: bool,
+        ) -> Addr {
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let native_token_info = TokenInfo::NativeToken {
+                denom: NATIVE_DENOM_2.to_string(),
+            };
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: native_token_info.clone(),
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add phase 1 by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddPhase {
+                    new_start_time: current_block_time + 110,
+                    new_end_time: current_block_time + 200,
+                    reward_tokens: vec![PhaseRewardToken {
+                        info: native_token_info,
+                        whitelist: Addr::unchecked(ADMIN.to_string()),
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let funded_phases: &[u64] = if funded { &[0, 1] } else { &[0] };
+            for &phase_index in funded_phases {
+                // Execute add 1000 NATIVE_2 reward balance by ADMIN
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::AddRewardBalance {
+                        phase_index,
+                        amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    },
+                    &[Coin {
+                        amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                        denom: NATIVE_DENOM_2.to_string(),
+                    }],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            halo_farm_contract_addr
+        }
+
+        // Create farm contract with two funded phases
+        // Deposit 1000 lp token by ADMIN at start time
+        // After 150 seconds phase 1 is activated without ActivatePhase
+        // -> phase 0 is settled at its end time: 1000 NATIVE_2 reward
+        // -> phase 1 has run for 40 seconds of 90 seconds: 444.444444 NATIVE_2 reward
+        // Harvest by ADMIN -> 1444.444444 NATIVE_2
+        // ActivatePhase by ADMIN -> NoPhaseToActivate
+        #[test]
+        fn proper_rollover_to_funded_phase() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let current_block_time = app.block_info().time.seconds();
+            let halo_farm_contract_addr =
+                create_farm_with_two_phases(&mut app, lp_token_contract, true);
+
+            // Execute deposit 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 150 seconds, it is after the start time of phase 1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(150),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query farm info
+            let farm_info: FarmInfoResponse = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+                .unwrap();
+
+            // Phase 1 is activated and phase 0 is settled at its end time
+            assert_eq!(farm_info.current_phase_index, 1);
+            assert_eq!(
+                farm_info.phases_info[0].last_reward_time,
+                current_block_time + 100
+            );
+            assert_eq!(
+                farm_info.phases_info[0].reward_tokens[0].accrued_token_per_share,
+                Decimal256::from_ratio(ACCRUED_TOKEN_PER_SHARE_SCALE, 1u128)
+            );
+
+            // Query pending reward of ADMIN
+            let pending_reward: PendingRewardResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::PendingReward {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                pending_reward.rewards[0].amount,
+                Uint128::from(1_444_444_444u128)
+            );
+
+            // Execute harvest by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives the reward of both phases
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(
+                balance.amount,
+                Uint128::from(NATIVE_BALANCE_2 - 2 * ADD_1000_NATIVE_BALANCE_2 + 1_444_444_444u128)
+            );
+
+            // Execute activate phase by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::ActivatePhase {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NoPhaseToActivate {}
+            );
+        }
+
+        // Create farm contract with phase 1 not funded
+        // After 150 seconds phase 1 is not activated
+        // Deposit 1000 lp token by ADMIN -> PhaseEnded
+        #[test]
+        fn no_rollover_to_empty_phase() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let current_block_time = app.block_info().time.seconds();
+            let halo_farm_contract_addr =
+                create_farm_with_two_phases(&mut app, lp_token_contract, false);
+
+            // change block time increase 150 seconds, it is after the start time of phase 1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(150),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query farm info
+            let farm_info: FarmInfoResponse = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+                .unwrap();
+
+            assert_eq!(farm_info.current_phase_index, 0);
+
+            // Execute deposit 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::PhaseEnded {
+                    phase_index: 0,
+                    end_time: current_block_time + 100,
+                }
+            );
+        }
+    }
+
+    mod extend_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Uint128};
+        use cw20::Cw20ExecuteMsg;
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{PendingRewardResponse, TokenInfo};
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_BALANCE_2,
+                NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Create farm contract with 1000 NATIVE_2 reward balance -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by ADMIN at start time
+        // After 50 seconds -> 500 NATIVE_2 reward
+        // Extend phase by USER_1 -> Unauthorized
+        // Extend phase to the same end time -> InvalidNewEndTime
+        // Top up 1000 NATIVE_2 -> 1500 NATIVE_2 left for 50 seconds
+        // Extend phase by 50 seconds -> 1500 NATIVE_2 left for 100 seconds
+        // After 100 seconds -> 2000 NATIVE_2 reward
+        // Extend phase after it ends -> PhaseEnded
+        #[test]
+        fn proper_extend_and_top_up_phase() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 50 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute extend phase by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::ExtendPhase {
+                    new_end_time: current_block_time + 150,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Unauthorized {}
+            );
+
+            // Execute extend phase to the same end time by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::ExtendPhase {
+                    new_end_time: current_block_time + 100,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidNewEndTime {
+                    end_time: current_block_time + 100,
+                    new_end_time: current_block_time + 100,
+                }
+            );
+
+            // Execute top up 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::TopUpActivePhase {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute extend phase by 50 seconds by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::ExtendPhase {
+                    new_end_time: current_block_time + 150,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query pending reward of ADMIN, the reward before the top up is not changed
+            let pending_reward: PendingRewardResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::PendingReward {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                pending_reward.rewards[0].amount,
+                Uint128::from(500_000_000u128)
+            );
+
+            // change block time increase 100 seconds to end the extended phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query pending reward of ADMIN
+            let pending_reward: PendingRewardResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::PendingReward {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                pending_reward.rewards[0].amount,
+                Uint128::from(2 * ADD_1000_NATIVE_BALANCE_2)
+            );
+
+            // Execute extend phase after it ends by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::ExtendPhase {
+                    new_end_time: current_block_time + 300,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::PhaseEnded {
+                    phase_index: 0,
+                    end_time: current_block_time + 150,
+                }
+            );
+
+            // Execute harvest by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives all the reward balance back
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(NATIVE_BALANCE_2));
+        }
+    }
+
+    mod limit_operation {
+        use cosmwasm_std::{to_binary, Addr, Coin, Uint128};
+        use cw20::Cw20ExecuteMsg;
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{FarmInfoResponse, StakingCapacityResponse, TokenInfo};
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Message to deposit lp token to the farm contract by cw20 send
+        fn deposit_msg(halo_farm_contract_addr: &Addr, amount: u128) -> Cw20ExecuteMsg {
+            Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                })
+                .unwrap(),
+            }
+        }
+
+        // Create farm contract with 1000 lp token limit per user
+        // Deposit 1000 lp token by ADMIN
+        // Update limit by USER_1 -> Unauthorized
+        // Update limit to 0 -> InvalidPhasesLimitPerUser
+        // Lower limit to 500 lp token
+        // Deposit 1 lp token by ADMIN -> ExceedsUserLimit
+        // Withdraw 100 lp token by ADMIN -> ADMIN keeps 900 lp token staked
+        // Deposit 500 lp token by USER_1, then 1 lp token -> ExceedsUserLimit
+        // Raise limit to 2000 lp token -> deposit 1100 lp token by ADMIN
+        // Remove limit -> deposit 2000 lp token by USER_1
+        #[test]
+        fn proper_update_phases_limit_per_user() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 2000 HALO LP tokens to ADMIN and 2500 HALO LP tokens to USER_1
+            for (recipient, amount) in [
+                (ADMIN, 2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                (USER_1, 5 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+            ] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(amount),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm with 1000 lp token limit per user
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: Some(Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT)),
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(&halo_farm_contract_addr, MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute update phases limit per user by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::UpdatePhasesLimitPerUser {
+                    new_phases_limit_per_user: None,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Unauthorized {}
+            );
+
+            // Execute update phases limit per user to 0 by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::UpdatePhasesLimitPerUser {
+                    new_phases_limit_per_user: Some(Uint128::zero()),
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidPhasesLimitPerUser {}
+            );
+
+            // Execute lower phases limit per user to 500 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::UpdatePhasesLimitPerUser {
+                    new_phases_limit_per_user: Some(Uint128::from(
+                        MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2,
+                    )),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query farm info
+            let farm_info: FarmInfoResponse = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+                .unwrap();
+
+            assert_eq!(
+                farm_info.phases_limit_per_user,
+                Some(Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2))
+            );
+
+            // ADMIN keeps 1000 lp token staked above the new limit but can not deposit more
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(&halo_farm_contract_addr, 1),
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::ExceedsUserLimit {
+                    limit: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                    requested: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT + 1),
+                }
+            );
+
+            // Execute withdraw 100 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 10),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 500 lp token by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(&halo_farm_contract_addr, MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1 lp token by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(&halo_farm_contract_addr, 1),
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::ExceedsUserLimit {
+                    limit: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                    requested: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2 + 1),
+                }
+            );
+
+            // Execute raise phases limit per user to 2000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::UpdatePhasesLimitPerUser {
+                    new_phases_limit_per_user: Some(Uint128::from(
+                        2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT,
+                    )),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1100 lp token by ADMIN -> 2000 lp token staked
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(
+                    &halo_farm_contract_addr,
+                    MOCK_1000_HALO_LP_TOKEN_AMOUNT + MOCK_1000_HALO_LP_TOKEN_AMOUNT / 10,
+                ),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute remove phases limit per user by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::UpdatePhasesLimitPerUser {
+                    new_phases_limit_per_user: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 2000 lp token by USER_1 -> 2500 lp token staked
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(&halo_farm_contract_addr, 2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query total staked
+            let total_staked: Uint128 = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr, &FarmQueryMsg::TotalStaked {})
+                .unwrap();
+
+            assert_eq!(
+                total_staked,
+                Uint128::from(
+                    2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT + 5 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2
+                )
+            );
+        }
+
+        // Create farm contract with 0 lp token max total staked -> InvalidMaxTotalStaked
+        // Create farm contract with 1500 lp token max total staked
+        // Deposit 1000 lp token by ADMIN -> 500 lp token remaining capacity
+        // Deposit 600 lp token by USER_1 -> ExceedsMaxTotalStaked
+        // Deposit 500 lp token by USER_1 -> no remaining capacity
+        // Withdraw 100 lp token by ADMIN -> 100 lp token remaining capacity
+        #[test]
+        fn proper_max_total_staked() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN and USER_1
+            for recipient in [ADMIN, USER_1] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm with 0 lp token max total staked
+            let mut halo_farm_instantiate_msg = FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: Some(Uint128::zero()),
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            let response = app.instantiate_contract(
+                halo_farm_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &halo_farm_instantiate_msg,
+                &[],
+                "instantiate contract",
+                None,
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidMaxTotalStaked {}
+            );
+
+            // create farm with 1500 lp token max total staked
+            halo_farm_instantiate_msg.max_total_staked =
+                Some(Uint128::from(3 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2));
+
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(&halo_farm_contract_addr, MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query staking capacity
+            let capacity: StakingCapacityResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::StakingCapacity {},
+                )
+                .unwrap();
+
+            assert_eq!(
+                capacity,
+                StakingCapacityResponse {
+                    total_staked: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    max_total_staked: Some(Uint128::from(3 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2)),
+                    remaining_capacity: Some(Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2)),
+                }
+            );
+
+            // Execute deposit 600 lp token by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(
+                    &halo_farm_contract_addr,
+                    6 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 10,
+                ),
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::ExceedsMaxTotalStaked {
+                    limit: Uint128::from(3 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                    requested: Uint128::from(16 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 10),
+                }
+            );
+
+            // Execute deposit 500 lp token by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(&halo_farm_contract_addr, MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query staking capacity
+            let capacity: StakingCapacityResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::StakingCapacity {},
+                )
+                .unwrap();
+
+            assert_eq!(capacity.remaining_capacity, Some(Uint128::zero()));
+
+            // Execute withdraw 100 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 10),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query staking capacity
+            let capacity: StakingCapacityResponse = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr, &FarmQueryMsg::StakingCapacity {})
+                .unwrap();
+
+            assert_eq!(
+                capacity.remaining_capacity,
+                Some(Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT / 10))
+            );
+        }
+    }
+
+    mod compound_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Uint128};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{StakerInfoResponse, TokenInfo};
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Create farm contract with HALO LP token as both staked token and reward token
+        // Add 1000 HALO LP token reward balance -> 10 HALO LP token per second
+        // Deposit 1000 HALO LP token by ADMIN at start time
+        // Compound by USER_1 -> NotStaker
+        // After 50 seconds compound by ADMIN -> 1500 HALO LP token staked
+        // After 50 seconds withdraw 1500 HALO LP token by ADMIN
+        // -> 499.999999 HALO LP token reward
+        #[test]
+        fn proper_compound() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 2000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::Token {
+                        contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 HALO LP token reward balance and deposit 1000 HALO LP token by ADMIN
+            for hook_msg in [
+                FarmCw20HookMsg::AddRewardBalance { phase_index: 0 },
+                FarmCw20HookMsg::Deposit {
+                    lock_duration: None,
+                },
+            ] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Send {
+                        contract: halo_farm_contract_addr.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                        msg: to_binary(&hook_msg).unwrap(),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // Execute compound by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Compound {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NotStaker {
+                    address: Addr::unchecked(USER_1)
+                }
+            );
+
+            // change block time increase 50 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute compound by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Compound {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query staker info of ADMIN
+            let staker_info: StakerInfoResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::StakerInfo {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            // 500 HALO LP token reward is added to the staked amount
+            assert_eq!(
+                staker_info.amount,
+                Uint128::from(3 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2)
+            );
+
+            // Query total staked
+            let total_staked: Uint128 = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::TotalStaked {},
+                )
+                .unwrap();
+
+            assert_eq!(
+                total_staked,
+                Uint128::from(3 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2)
+            );
+
+            // change block time increase 50 seconds to end the phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute withdraw 1500 HALO LP token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(3 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives the compounded stake and the reward of the last 50 seconds
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    lp_token_contract.clone(),
+                    &Cw20QueryMsg::Balance {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                balance.balance,
+                Uint128::from(3 * MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2 + 499_999_999u128)
+            );
+        }
+
+        // Create farm contract with NATIVE_2 reward token
+        // Deposit 1000 HALO LP token by ADMIN
+        // Compound by ADMIN -> CompoundNotSupported
+        #[test]
+        fn compound_without_staked_reward_token() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute compound by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::Compound {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::CompoundNotSupported {
+                    token: lp_token_contract.to_string(),
+                }
+            );
+        }
+    }
+
+    mod operator_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Uint128};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{OperatorResponse, OperatorsResponse, TokenInfo};
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        const TREASURY: &str = "aura1000000000000000000000000000000treasury";
+
+        // Create farm contract with NATIVE_2 reward token
+        // Add 1000 NATIVE_2 reward balance -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by ADMIN
+        // Harvest for ADMIN by USER_1 -> OperatorNotApproved
+        // ADMIN approves USER_1 as operator with TREASURY as recipient
+        // After 50 seconds harvest for ADMIN by USER_1 -> TREASURY receives 500 NATIVE_2
+        // After 25 seconds harvest by ADMIN to USER_1 -> USER_1 receives 250 NATIVE_2
+        // ADMIN revokes USER_1 -> harvest for ADMIN by USER_1 -> OperatorNotApproved
+        // After 25 seconds withdraw 1000 lp token by ADMIN to TREASURY
+        // -> TREASURY receives 1000 lp token and 750 NATIVE_2
+        #[test]
+        fn proper_harvest_by_operator_to_recipient() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm
+            let halo_farm_instantiate_msg = &FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            // instantiate contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute harvest for ADMIN by USER_1 before the approval
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::HarvestFor {
+                    staker: ADMIN.to_string(),
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::OperatorNotApproved {
+                    operator: Addr::unchecked(USER_1),
+                    staker: Addr::unchecked(ADMIN),
+                }
+            );
+
+            // Execute approve USER_1 as operator with TREASURY as recipient by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::ApproveOperator {
+                    operator: USER_1.to_string(),
+                    recipient: Some(TREASURY.to_string()),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query operators of ADMIN
+            let operators: OperatorsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::Operators {
+                        staker: ADMIN.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                operators,
+                OperatorsResponse {
+                    operators: vec![OperatorResponse {
+                        operator: Addr::unchecked(USER_1),
+                        recipient: Addr::unchecked(TREASURY),
+                    }],
+                }
+            );
+
+            // change block time increase 50 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute harvest for ADMIN by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::HarvestFor {
+                    staker: ADMIN.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // TREASURY receives the reward of the first 50 seconds
+            let balance = app.wrap().query_balance(TREASURY, NATIVE_DENOM_2).unwrap();
+            assert_eq!(balance.amount, Uint128::from(500_000_000u128));
+
+            // change block time increase 25 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(25),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute harvest to USER_1 by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Harvest {
+                    recipient: Some(USER_1.to_string()),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 receives the reward of the next 25 seconds
+            let balance = app.wrap().query_balance(USER_1, NATIVE_DENOM_2).unwrap();
+            assert_eq!(balance.amount, Uint128::from(250_000_000u128));
+
+            // Execute revoke USER_1 by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::RevokeOperator {
+                    operator: USER_1.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute harvest for ADMIN by USER_1 after the revocation
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::HarvestFor {
+                    staker: ADMIN.to_string(),
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::OperatorNotApproved {
+                    operator: Addr::unchecked(USER_1),
+                    staker: Addr::unchecked(ADMIN),
+                }
+            );
+
+            // change block time increase 25 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(25),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute withdraw 1000 lp token to TREASURY by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    recipient: Some(TREASURY.to_string()),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // TREASURY receives the staked lp token and the reward of the last 25 seconds
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    lp_token_contract.clone(),
+                    &Cw20QueryMsg::Balance {
+                        address: TREASURY.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                balance.balance,
+                Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT)
+            );
+
+            let balance = app.wrap().query_balance(TREASURY, NATIVE_DENOM_2).unwrap();
+            assert_eq!(balance.amount, Uint128::from(750_000_000u128));
+        }
+    }
+
+    mod lock_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Decimal, Uint128};
+        use cw20::Cw20ExecuteMsg;
+        use cw_multi_test::Executor;
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{
+            FarmInfoResponse, Lock, LockTier, LocksResponse, PendingRewardResponse,
+            RewardTokenAsset, TokenInfo,
+        };
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_BALANCE_2,
+                NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        // Message to deposit lp token to the farm contract by cw20 send
+        fn deposit_msg(
+            halo_farm_contract_addr: &Addr,
+            amount: u128,
+            lock_duration: Option<u64>,
+        ) -> Cw20ExecuteMsg {
+            Cw20ExecuteMsg::Send {
+                contract: halo_farm_contract_addr.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&FarmCw20HookMsg::Deposit { lock_duration }).unwrap(),
+            }
+        }
+
+        // Create farm contract with a lock tier multiplier less than 1 -> InvalidLockTiers
+        // Create farm contract with 50 seconds lock tier and x3 multiplier
+        // Update lock tiers by USER_1 -> Unauthorized
+        // Add 1000 NATIVE_2 reward balance -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by ADMIN without lock
+        // Deposit 1000 lp token by USER_1 with 10 seconds lock -> InvalidLockDuration
+        // Deposit 1000 lp token by USER_1 with 50 seconds lock -> weight 3000
+        // Withdraw and emergency withdraw by USER_1 -> LockedStake
+        // After 50 seconds withdraw 1000 lp token by USER_1 -> 375 NATIVE_2 reward
+        // After 50 seconds ADMIN has 125 + 500 NATIVE_2 pending reward
+        #[test]
+        fn proper_lock_with_boosted_weight() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN and USER_1
+            for recipient in [ADMIN, USER_1] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm with a lock tier multiplier less than 1
+            let mut halo_farm_instantiate_msg = FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![LockTier {
+                    duration: 50,
+                    multiplier: Decimal::percent(50),
+                }],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            let response = app.instantiate_contract(
+                halo_farm_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &halo_farm_instantiate_msg,
+                &[],
+                "instantiate contract",
+                None,
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidLockTiers {}
+            );
+
+            // create farm with 50 seconds lock tier and x3 multiplier
+            halo_farm_instantiate_msg.lock_tiers = vec![LockTier {
+                duration: 50,
+                multiplier: Decimal::from_ratio(3u128, 1u128),
+            }];
+
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute update lock tiers by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::UpdateLockTiers {
+                    new_lock_tiers: vec![],
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::Unauthorized {}
+            );
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN without lock
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(
+                    &halo_farm_contract_addr,
+                    MOCK_1000_HALO_LP_TOKEN_AMOUNT,
+                    None,
+                ),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by USER_1 with 10 seconds lock
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(
+                    &halo_farm_contract_addr,
+                    MOCK_1000_HALO_LP_TOKEN_AMOUNT,
+                    Some(10),
+                ),
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidLockDuration { duration: 10 }
+            );
+
+            // Execute deposit 1000 lp token by USER_1 with 50 seconds lock
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(
+                    &halo_farm_contract_addr,
+                    MOCK_1000_HALO_LP_TOKEN_AMOUNT,
+                    Some(50),
+                ),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Query locks of USER_1
+            let locks: LocksResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::Locks {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                locks,
+                LocksResponse {
+                    locks: vec![Lock {
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                        multiplier: Decimal::from_ratio(3u128, 1u128),
+                        unlock_time: current_block_time + 50,
+                    }],
+                    locked_amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    weight: Uint128::from(3 * MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    time_query: current_block_time,
+                }
+            );
+
+            // Execute withdraw 1 lp token by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::one(),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::LockedStake {
+                    unlocked: Uint128::zero(),
+                    requested: Uint128::one(),
+                }
+            );
+
+            // Execute emergency withdraw by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::EmergencyWithdraw {},
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::LockedStake {
+                    unlocked: Uint128::zero(),
+                    requested: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                }
+            );
+
+            // change block time increase 50 seconds to unlock
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute withdraw 1000 lp token by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    recipient: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 receives 3/4 of the reward of the first 50 seconds
+            let balance = app.wrap().query_balance(USER_1, NATIVE_DENOM_2).unwrap();
+            assert_eq!(balance.amount, Uint128::from(375_000_000u128));
+
+            // Query locks of USER_1
+            let locks: LocksResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::Locks {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                locks,
+                LocksResponse {
+                    locks: vec![],
+                    locked_amount: Uint128::zero(),
+                    weight: Uint128::zero(),
+                    time_query: current_block_time + 50,
+                }
+            );
+
+            // change block time increase 50 seconds to end the phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query pending reward of ADMIN
+            let pending_reward: PendingRewardResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr,
+                    &FarmQueryMsg::PendingReward {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            // ADMIN has 1/4 of the first 50 seconds and all of the last 50 seconds
+            assert_eq!(
+                pending_reward.rewards,
+                vec![RewardTokenAsset {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    amount: Uint128::from(625_000_000u128),
+                }]
+            );
+        }
+
+        // Create farm contract with 150 seconds lock tier and x3 multiplier
+        // Add a second phase, add 1000 NATIVE_2 reward balance to each phase -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by ADMIN without lock
+        // Deposit 1000 lp token by USER_1 with 150 seconds lock -> weight 3000
+        // USER_1 stays idle after the unlock time in the second phase
+        // -> the boost weight is removed from the farm at the unlock time
+        // At the end of the second phase ADMIN has 250 + 125 + 250 NATIVE_2 pending reward
+        // Harvest by USER_1 -> 750 + 375 + 250 NATIVE_2 reward
+        #[test]
+        fn proper_boost_expires_at_unlock_time() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN and USER_1
+            for recipient in [ADMIN, USER_1] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let reward_tokens = vec![PhaseRewardToken {
+                info: TokenInfo::NativeToken {
+                    denom: NATIVE_DENOM_2.to_string(),
+                },
+                whitelist: Addr::unchecked(ADMIN.to_string()),
+            }];
+
+            // create farm with 150 seconds lock tier and x3 multiplier
+            let halo_farm_instantiate_msg = FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: reward_tokens.clone(),
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![LockTier {
+                    duration: 150,
+                    multiplier: Decimal::from_ratio(3u128, 1u128),
+                }],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add a second phase by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddPhase {
+                    new_start_time: current_block_time + 100,
+                    new_end_time: current_block_time + 200,
+                    reward_tokens,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute add 1000 NATIVE_2 reward balance to each phase by ADMIN
+            for phase_index in 0..2u64 {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::AddRewardBalance {
+                        phase_index,
+                        amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    },
+                    &[Coin {
+                        amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                        denom: NATIVE_DENOM_2.to_string(),
+                    }],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // Execute deposit 1000 lp token by ADMIN without lock
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(
+                    &halo_farm_contract_addr,
+                    MOCK_1000_HALO_LP_TOKEN_AMOUNT,
+                    None,
+                ),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by USER_1 with 150 seconds lock
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &deposit_msg(
+                    &halo_farm_contract_addr,
+                    MOCK_1000_HALO_LP_TOKEN_AMOUNT,
+                    Some(150),
+                ),
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // change block time increase 150 seconds to unlock in the second phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(150),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // The boost weight of USER_1 is removed without any action of USER_1
+            let farm_info: FarmInfoResponse = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+                .unwrap();
+
+            assert_eq!(farm_info.current_phase_index, 1);
+            assert_eq!(
+                farm_info.staked_token_balance,
+                Uint128::from(2 * MOCK_1000_HALO_LP_TOKEN_AMOUNT)
+            );
+            assert_eq!(farm_info.boost_weight, Uint128::zero());
+
+            // Query locks of USER_1
+            let locks: LocksResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::Locks {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                locks,
+                LocksResponse {
+                    locks: vec![],
+                    locked_amount: Uint128::zero(),
+                    weight: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    time_query: current_block_time + 150,
+                }
+            );
+
+            // change block time increase 50 seconds to end the second phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query pending reward of ADMIN
+            let pending_reward: PendingRewardResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::PendingReward {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            // ADMIN has 1/4 of the first phase, 1/4 of the second phase until the unlock time
+            // and 1/2 of the second phase after the unlock time
+            assert_eq!(
+                pending_reward.rewards,
+                vec![RewardTokenAsset {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    amount: Uint128::from(625_000_000u128),
+                }]
+            );
+
+            // Execute harvest by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 receives the boosted reward only until the unlock time
+            let balance = app.wrap().query_balance(USER_1, NATIVE_DENOM_2).unwrap();
+            assert_eq!(balance.amount, Uint128::from(1_375_000_000u128));
+
+            // Query pending reward of ADMIN after the harvest of USER_1
+            let pending_reward: PendingRewardResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr,
+                    &FarmQueryMsg::PendingReward {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                pending_reward.rewards[0].amount,
+                Uint128::from(625_000_000u128)
+            );
+        }
+
+        // Create farm contract with 50 and 60 seconds lock tiers and x3 multiplier
+        // Add 1000 NATIVE_2 reward balance -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by ADMIN without lock
+        // Deposit 500 lp token by USER_1 with 50 seconds lock and 500 with 60 seconds lock
+        // -> weight 3000
+        // Harvest by ADMIN after 55 seconds -> 125 + 16.666666 NATIVE_2 reward,
+        // the boost of the 50 seconds lock is expired
+        // Emergency withdraw by USER_1 after 70 seconds -> 1000 lp token back,
+        // the boost of the 60 seconds lock is removed before its expiry is processed
+        // Harvest by ADMIN at the end of the phase -> 450 NATIVE_2 reward
+        #[test]
+        fn proper_emergency_withdraw_after_unlock_time() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN and USER_1
+            for recipient in [ADMIN, USER_1] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm with 50 and 60 seconds lock tiers and x3 multiplier
+            let halo_farm_instantiate_msg = FarmInstantiateMsg {
+                staked_token: TokenInfo::Token {
+                    contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                },
+                reward_tokens: vec![PhaseRewardToken {
+                    info: TokenInfo::NativeToken {
+                        denom: NATIVE_DENOM_2.to_string(),
+                    },
+                    whitelist: Addr::unchecked(ADMIN.to_string()),
+                }],
+                start_time: current_block_time,
+                end_time: current_block_time + 100,
+                phases_limit_per_user: None,
+                max_total_staked: None,
+                lock_tiers: vec![
+                    LockTier {
+                        duration: 50,
+                        multiplier: Decimal::from_ratio(3u128, 1u128),
+                    },
+                    LockTier {
+                        duration: 60,
+                        multiplier: Decimal::from_ratio(3u128, 1u128),
+                    },
+                ],
+                farm_owner: Addr::unchecked(ADMIN.to_string()),
+            };
+
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &halo_farm_instantiate_msg,
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add 1000 NATIVE_2 reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                },
+                &[Coin {
+                    amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit 1000 lp token by ADMIN without lock
+            // and 500 lp token by USER_1 with each lock tier
+            for (staker, amount, lock_duration) in [
+                (ADMIN, MOCK_1000_HALO_LP_TOKEN_AMOUNT, None),
+                (USER_1, MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2, Some(50)),
+                (USER_1, MOCK_1000_HALO_LP_TOKEN_AMOUNT / 2, Some(60)),
+            ] {
+                let response = app.execute_contract(
+                    Addr::unchecked(staker.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &deposit_msg(&halo_farm_contract_addr, amount, lock_duration),
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // change block time increase 55 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(55),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute harvest by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives 1/4 of the reward of the first 50 seconds
+            // and 1/3 of the reward of the next 5 seconds
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(
+                balance.amount,
+                Uint128::from(NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2 + 141_666_666u128)
+            );
+
+            // change block time increase 15 seconds
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(15),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute emergency withdraw by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::EmergencyWithdraw {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 gets all lp token back
+            let balance: cw20::BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    lp_token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                balance.balance,
+                Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT)
+            );
+
+            // Only the weight of ADMIN is left
+            let farm_info: FarmInfoResponse = app
+                .wrap()
+                .query_wasm_smart(halo_farm_contract_addr.clone(), &FarmQueryMsg::Farm {})
+                .unwrap();
+
+            assert_eq!(
+                farm_info.staked_token_balance,
+                Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT)
+            );
+            assert_eq!(farm_info.boost_weight, Uint128::zero());
+
+            // change block time increase 30 seconds to end the phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(30),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute harvest by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr,
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // ADMIN receives the whole reward of the last 45 seconds
+            let balance = app.wrap().query_balance(ADMIN, NATIVE_DENOM_2).unwrap();
+
+            assert_eq!(
+                balance.amount,
+                Uint128::from(
+                    NATIVE_BALANCE_2 - ADD_1000_NATIVE_BALANCE_2
+                        + 141_666_666u128
+                        + 450_000_000u128
+                )
+            );
+        }
+    }
+
+    // create farm contract
+    // let a staker be idle across many phases
+    // settle the ended phases in several messages
+    mod settle_phases_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Timestamp, Uint128};
+        use cw20::Cw20ExecuteMsg;
+        use cw_multi_test::{App, Executor};
+
+        use crate::error::ContractError;
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken, QueryMsg as FarmQueryMsg,
+        };
+        use crate::state::{
+            AllStakersResponse, PendingRewardResponse, StakerInfoResponse, TokenInfo,
+        };
+        use crate::tests::{
+            env_setup::env::{
+                halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_DENOM_2, USER_1,
+            },
+            integration_test::tests::{ADD_1000_NATIVE_BALANCE_2, MOCK_1000_HALO_LP_TOKEN_AMOUNT},
+        };
+
+        const PHASES_COUNT: u64 = 121;
+
+        fn query_pending_reward(app: &App, farm: &Addr) -> Uint128 {
+            let pending_reward: PendingRewardResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    farm.clone(),
+                    &FarmQueryMsg::PendingReward {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+            pending_reward.rewards[0].amount
+        }
+
+        fn query_reward_balance(app: &App) -> Uint128 {
+            app.wrap()
+                .query_balance(USER_1, NATIVE_DENOM_2)
+                .unwrap()
+                .amount
+        }
+
+        // Create farm contract with 1000 NATIVE_2 reward balance for 100 seconds -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by USER_1 after 1 second
+        // Add and fund 120 more phases of 100 seconds by ADMIN while USER_1 is idle
+        // After 50 seconds of the last phase, query pending reward, harvest and withdraw
+        // by USER_1 -> TooManyPhasesToSettle
+        // Settle up to phase 121 or by ADMIN -> error
+        // Settle up to phase 60 by USER_1 -> 990 + 59 * 1000 NATIVE_2
+        // Settle up to phase 60 again by USER_1 -> InvalidSettlePhases
+        // Pause by ADMIN, settle up to phase 120 by USER_1 -> no reward while paused
+        // Unpause by ADMIN, harvest by USER_1 -> 60 * 1000 + 500 NATIVE_2
+        #[test]
+        fn proper_settle_phases_after_idle_across_many_phases() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: USER_1.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let reward_tokens = vec![PhaseRewardToken {
+                info: TokenInfo::NativeToken {
+                    denom: NATIVE_DENOM_2.to_string(),
+                },
+                whitelist: Addr::unchecked(ADMIN.to_string()),
+            }];
+
+            // create farm contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &FarmInstantiateMsg {
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                        },
+                        reward_tokens: reward_tokens.clone(),
+                        start_time: current_block_time,
+                        end_time: current_block_time + 100,
+                        phases_limit_per_user: None,
+                        max_total_staked: None,
+                        lock_tiers: vec![],
+                        farm_owner: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Add 1000 NATIVE_2 reward balance to the phase
+            let add_reward_balance = |app: &mut App, phase_index: u64| {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::AddRewardBalance {
+                        phase_index,
+                        amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    },
+                    &[Coin {
+                        amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                        denom: NATIVE_DENOM_2.to_string(),
+                    }],
+                );
+
+                assert!(response.is_ok());
+            };
+
+            add_reward_balance(&mut app, 0);
+
+            // change block time increase 1 second
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute deposit 1000 lp token by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(lp_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    msg: to_binary(&FarmCw20HookMsg::Deposit {
+                        lock_duration: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Add and fund the next phase by ADMIN after the previous phase started
+            for phase_index in 1..PHASES_COUNT {
+                app.set_block(BlockInfo {
+                    time: Timestamp::from_seconds(current_block_time + (phase_index - 1) * 100 + 1),
+                    height: app.block_info().height + 1,
+                    chain_id: app.block_info().chain_id,
+                });
+
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::AddPhase {
+                        new_start_time: current_block_time + phase_index * 100,
+                        new_end_time: current_block_time + (phase_index + 1) * 100,
+                        reward_tokens: reward_tokens.clone(),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+
+                add_reward_balance(&mut app, phase_index);
+            }
+
+            // change block time to the middle of the last phase
+            app.set_block(BlockInfo {
+                time: Timestamp::from_seconds(current_block_time + (PHASES_COUNT - 1) * 100 + 50),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // The pending reward of USER_1 can not be computed across 120 phases
+            let too_many_phases_error = ContractError::TooManyPhasesToSettle {
+                joined_phase: 0,
+                current_phase_index: 120,
+            };
+            let error = app
+                .wrap()
+                .query_wasm_smart::<PendingRewardResponse>(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::PendingReward {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap_err();
+            assert!(error
+                .to_string()
+                .contains(&too_many_phases_error.to_string()));
+
+            // Execute harvest and withdraw by USER_1
+            for msg in [
+                FarmExecuteMsg::Harvest { recipient: None },
+                FarmExecuteMsg::Withdraw {
+                    amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    recipient: None,
+                },
+            ] {
+                let response = app.execute_contract(
+                    Addr::unchecked(USER_1.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &msg,
+                    &[],
+                );
+
+                assert_eq!(
+                    response.unwrap_err().downcast::<ContractError>().unwrap(),
+                    too_many_phases_error
+                );
+            }
+
+            // Execute settle up to the phase after the current phase by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::SettlePhases { up_to: 121 },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidSettlePhases {
+                    up_to: 121,
+                    joined_phase: 0,
+                    current_phase_index: 120,
+                }
+            );
+
+            // Execute settle phases by ADMIN who is not a staker
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::SettlePhases { up_to: 60 },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::NotStaker {
+                    address: Addr::unchecked(ADMIN)
+                }
+            );
+
+            // Execute settle up to phase 60 by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::SettlePhases { up_to: 60 },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1: 99 seconds * 10 NATIVE_2 + 59 phases * 1000 NATIVE_2
+            assert_eq!(
+                query_reward_balance(&app),
+                Uint128::from(59_990_000_000u128)
+            );
+
+            // USER_1 joins phase 60
+            let staker_info: StakerInfoResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    halo_farm_contract_addr.clone(),
+                    &FarmQueryMsg::StakerInfo {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(staker_info.joined_phase, 60);
+
+            // USER_1: 60 phases * 1000 NATIVE_2 + 50 seconds * 10 NATIVE_2
+            assert_eq!(
+                query_pending_reward(&app, &halo_farm_contract_addr),
+                Uint128::from(60_500_000_000u128)
+            );
+
+            // Execute settle up to phase 60 again by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::SettlePhases { up_to: 60 },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast::<ContractError>().unwrap(),
+                ContractError::InvalidSettlePhases {
+                    up_to: 60,
+                    joined_phase: 60,
+                    current_phase_index: 120,
+                }
+            );
+
+            // Execute pause by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Pause {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute settle up to the current phase by USER_1 while paused
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::SettlePhases { up_to: 120 },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // The reward of the settled phases is not paid while paused
+            assert_eq!(
+                query_reward_balance(&app),
+                Uint128::from(59_990_000_000u128)
+            );
+
+            // Execute unpause by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Unpause {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute harvest by USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::Harvest { recipient: None },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1: 59990 NATIVE_2 + 60 phases * 1000 NATIVE_2
+            // + 50 seconds * 10 NATIVE_2 of the current phase
+            assert_eq!(
+                query_reward_balance(&app),
+                Uint128::from(120_490_000_000u128)
+            );
+        }
+
+        // Create farm contract with 1000 NATIVE_2 reward balance for 100 seconds -> 10 NATIVE_2 per second
+        // Deposit 1000 lp token by ADMIN and USER_1 after 1 second
+        // Add and fund 60 more phases of 100 seconds by ADMIN while both stakers are idle
+        // After 50 seconds of the last phase query all stakers
+        // -> only ADMIN, 60 phases to settle for each staker exceed the maximum of a page
+        // Query all stakers after ADMIN -> USER_1
+        // Both stakers have 495 + 59 * 500 + 250 NATIVE_2 pending reward
+        #[test]
+        fn proper_query_all_stakers_across_many_phases() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint 1000 HALO LP tokens to ADMIN and USER_1
+            for recipient in [ADMIN, USER_1] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let reward_tokens = vec![PhaseRewardToken {
+                info: TokenInfo::NativeToken {
+                    denom: NATIVE_DENOM_2.to_string(),
+                },
+                whitelist: Addr::unchecked(ADMIN.to_string()),
+            }];
+
+            // create farm contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &FarmInstantiateMsg {
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                        },
+                        reward_tokens: reward_tokens.clone(),
+                        start_time: current_block_time,
+                        end_time: current_block_time + 100,
+                        phases_limit_per_user: None,
+                        max_total_staked: None,
+                        lock_tiers: vec![],
+                        farm_owner: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Add 1000 NATIVE_2 reward balance to the phase
+            let add_reward_balance = |app: &mut App, phase_index: u64| {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::AddRewardBalance {
+                        phase_index,
+                        amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                    },
+                    &[Coin {
+                        amount: Uint128::from(ADD_1000_NATIVE_BALANCE_2),
+                        denom: NATIVE_DENOM_2.to_string(),
+                    }],
+                );
+
+                assert!(response.is_ok());
+            };
+
+            add_reward_balance(&mut app, 0);
+
+            // change block time increase 1 second
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute deposit 1000 lp token by ADMIN and USER_1
+            for staker in [ADMIN, USER_1] {
+                let response = app.execute_contract(
+                    Addr::unchecked(staker.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Send {
+                        contract: halo_farm_contract_addr.to_string(),
+                        amount: Uint128::from(MOCK_1000_HALO_LP_TOKEN_AMOUNT),
+                        msg: to_binary(&FarmCw20HookMsg::Deposit {
+                            lock_duration: None,
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // Add and fund the next phase by ADMIN after the previous phase started
+            for phase_index in 1..=60 {
+                app.set_block(BlockInfo {
+                    time: Timestamp::from_seconds(current_block_time + (phase_index - 1) * 100 + 1),
+                    height: app.block_info().height + 1,
+                    chain_id: app.block_info().chain_id,
+                });
+
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::AddPhase {
+                        new_start_time: current_block_time + phase_index * 100,
+                        new_end_time: current_block_time + (phase_index + 1) * 100,
+                        reward_tokens: reward_tokens.clone(),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+
+                add_reward_balance(&mut app, phase_index);
+            }
+
+            // change block time to the middle of the last phase
+            app.set_block(BlockInfo {
+                time: Timestamp::from_seconds(current_block_time + 60 * 100 + 50),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Query all stakers page by page
+            let query_all_stakers = |app: &App, start_after: Option<&str>| {
+                let all_stakers: AllStakersResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        halo_farm_contract_addr.clone(),
+                        &FarmQueryMsg::AllStakers {
+                            start_after: start_after.map(|staker| staker.to_string()),
+                            limit: None,
+                        },
+                    )
+                    .unwrap();
+                all_stakers.stakers
+            };
+
+            // The phases to settle for USER_1 do not fit in the first page
+            let mut stakers = query_all_stakers(&app, None);
+            assert_eq!(stakers.len(), 1);
+            assert_eq!(stakers[0].address, Addr::unchecked(ADMIN));
+
+            stakers.extend(query_all_stakers(&app, Some(ADMIN)));
+            assert_eq!(stakers.len(), 2);
+            assert_eq!(stakers[1].address, Addr::unchecked(USER_1));
+
+            // 99 seconds * 5 NATIVE_2 + 59 phases * 500 NATIVE_2 + 50 seconds * 5 NATIVE_2
+            for staker in stakers {
+                assert_eq!(
+                    staker.pending_rewards[0].amount,
+                    Uint128::from(30_245_000_000u128)
+                );
+            }
+        }
+    }
+
+    // create farm contract with a large staked token supply of 18 decimals
+    // harvest the reward in short intervals
+    // the total paid reward converges to the funded reward
+    mod precision_operation {
+        use cosmwasm_std::{to_binary, Addr, BlockInfo, Coin, Timestamp, Uint128};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+        use cw_multi_test::{App, Executor};
+
+        use crate::msg::{
+            Cw20HookMsg as FarmCw20HookMsg, ExecuteMsg as FarmExecuteMsg,
+            InstantiateMsg as FarmInstantiateMsg, PhaseRewardToken,
+        };
+        use crate::state::TokenInfo;
+        use crate::tests::env_setup::env::{
+            halo_farm_contract_template, instantiate_contracts, ADMIN, NATIVE_BALANCE_2,
+            NATIVE_DENOM_2, USER_1,
+        };
+
+        // 1_000_000_000 staked tokens of 18 decimals
+        const ADMIN_STAKED_AMOUNT: u128 = 1_000_000_000_000_000_000_000_000_000;
+        // 1000 staked tokens of 18 decimals
+        const USER_1_STAKED_AMOUNT: u128 = 1_000_000_000_000_000_000_000;
+        // All NATIVE_2 of ADMIN but 3 units, not divisible by the duration
+        const FUNDED_REWARD: u128 = NATIVE_BALANCE_2 - 3;
+
+        fn query_reward_balance(app: &App, address: &str) -> u128 {
+            app.wrap()
+                .query_balance(address, NATIVE_DENOM_2)
+                .unwrap()
+                .amount
+                .u128()
+        }
+
+        // Create farm contract starting after 10 seconds for 100 seconds
+        // Add FUNDED_REWARD NATIVE_2 reward balance
+        // Deposit 10^27 lp token by ADMIN and 10^21 lp token by USER_1 before the start time
+        // Harvest by USER_1 every 3 seconds and by ADMIN once in the middle of the phase
+        // Withdraw all lp token by ADMIN and USER_1 after the phase ends
+        // Each staker is paid its share of FUNDED_REWARD rounded down by at most 1 unit
+        #[test]
+        fn proper_reward_converges_to_funded_reward() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+
+            // Mint lp token to ADMIN and USER_1
+            for (recipient, amount) in
+                [(ADMIN, ADMIN_STAKED_AMOUNT), (USER_1, USER_1_STAKED_AMOUNT)]
+            {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(amount),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &FarmInstantiateMsg {
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                        },
+                        reward_tokens: vec![PhaseRewardToken {
+                            info: TokenInfo::NativeToken {
+                                denom: NATIVE_DENOM_2.to_string(),
+                            },
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                        }],
+                        start_time: current_block_time + 10,
+                        end_time: current_block_time + 110,
+                        phases_limit_per_user: None,
+                        max_total_staked: None,
+                        lock_tiers: vec![],
+                        farm_owner: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                halo_farm_contract_addr.clone(),
+                &FarmExecuteMsg::AddRewardBalance {
+                    phase_index: 0u64,
+                    amount: Uint128::from(FUNDED_REWARD),
+                },
+                &[Coin {
+                    amount: Uint128::from(FUNDED_REWARD),
+                    denom: NATIVE_DENOM_2.to_string(),
+                }],
+            );
+
+            assert!(response.is_ok());
+
+            // Execute deposit lp token by ADMIN and USER_1
+            for (depositor, amount) in
+                [(ADMIN, ADMIN_STAKED_AMOUNT), (USER_1, USER_1_STAKED_AMOUNT)]
+            {
+                let response = app.execute_contract(
+                    Addr::unchecked(depositor.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Send {
+                        contract: halo_farm_contract_addr.to_string(),
+                        amount: Uint128::from(amount),
+                        msg: to_binary(&FarmCw20HookMsg::Deposit {
+                            lock_duration: None,
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // Execute harvest by USER_1 every 3 seconds and by ADMIN in the middle of the phase
+            for seconds in (13..110).step_by(3) {
+                app.set_block(BlockInfo {
+                    time: Timestamp::from_seconds(current_block_time + seconds),
+                    height: app.block_info().height + 1,
+                    chain_id: app.block_info().chain_id,
+                });
+
+                let harvesters = if seconds == 61 {
+                    vec![USER_1, ADMIN]
+                } else {
+                    vec![USER_1]
+                };
+                for harvester in harvesters {
+                    let response = app.execute_contract(
+                        Addr::unchecked(harvester.to_string()),
+                        halo_farm_contract_addr.clone(),
+                        &FarmExecuteMsg::Harvest { recipient: None },
+                        &[],
+                    );
+
+                    assert!(response.is_ok());
+                }
+            }
+
+            // change block time to after the end time
+            app.set_block(BlockInfo {
+                time: Timestamp::from_seconds(current_block_time + 200),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute withdraw all lp token by ADMIN and USER_1
+            for (withdrawer, amount) in
+                [(ADMIN, ADMIN_STAKED_AMOUNT), (USER_1, USER_1_STAKED_AMOUNT)]
+            {
+                let response = app.execute_contract(
+                    Addr::unchecked(withdrawer.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::Withdraw {
+                        amount: Uint128::from(amount),
+                        recipient: None,
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // ADMIN has 3 NATIVE_2 left after funding the phase
+            let admin_reward = query_reward_balance(&app, ADMIN) - 3;
+            let user_1_reward = query_reward_balance(&app, USER_1);
+
+            // Each staker is paid its share rounded down, losing at most 1 unit
+            let total_staked = ADMIN_STAKED_AMOUNT + USER_1_STAKED_AMOUNT;
+            for (reward, staked_amount) in [
+                (admin_reward, ADMIN_STAKED_AMOUNT),
+                (user_1_reward, USER_1_STAKED_AMOUNT),
+            ] {
+                let share = Uint128::from(FUNDED_REWARD)
+                    .multiply_ratio(staked_amount, total_staked)
+                    .u128();
+                assert!(reward <= share && reward + 1 >= share);
+            }
+
+            // The total paid reward converges to the funded reward
+            assert!(admin_reward + user_1_reward <= FUNDED_REWARD);
+            assert!(admin_reward + user_1_reward + 2 >= FUNDED_REWARD);
+        }
+
+        // 10 reward tokens of 18 decimals per second
+        const GRIEFING_REWARD_PER_SECOND: u128 = 10_000_000_000_000_000_000;
+        // 100 staked tokens of 18 decimals
+        const LARGE_STAKED_AMOUNT: u128 = 100_000_000_000_000_000_000;
+
+        // Create farm contract starting after 10 seconds for 100 seconds
+        // Add 100 * GRIEFING_REWARD_PER_SECOND HALO reward balance
+        // Deposit 1 lp token unit by USER_1 before the start time
+        // After 1 second the accrued token per share is GRIEFING_REWARD_PER_SECOND per unit
+        // Deposit LARGE_STAKED_AMOUNT lp token by ADMIN -> the reward debt exceeds Uint128
+        // Harvest by ADMIN and USER_1 after 1 more second
+        // -> each staker is paid its share of the second second rounded down by at most 1 unit
+        #[test]
+        fn proper_deposit_after_large_accrued_token_per_share() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            // get farm contract code id
+            let halo_farm_contract_code_id = app.store_code(halo_farm_contract_template());
+            // get halo lp token contract
+            let lp_token_contract = &contracts[0].contract_addr;
+            // get halo reward token contract
+            let reward_token_contract = &contracts[1].contract_addr;
+
+            // Mint lp token to ADMIN and USER_1, and reward token to ADMIN
+            for (token_contract, recipient, amount) in [
+                (lp_token_contract, ADMIN, LARGE_STAKED_AMOUNT),
+                (lp_token_contract, USER_1, 1),
+                (
+                    reward_token_contract,
+                    ADMIN,
+                    100 * GRIEFING_REWARD_PER_SECOND,
+                ),
+            ] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(token_contract.clone()),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount: Uint128::from(amount),
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create farm contract
+            let halo_farm_contract_addr = app
+                .instantiate_contract(
+                    halo_farm_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &FarmInstantiateMsg {
+                        staked_token: TokenInfo::Token {
+                            contract_addr: Addr::unchecked(lp_token_contract.clone()),
+                        },
+                        reward_tokens: vec![PhaseRewardToken {
+                            info: TokenInfo::Token {
+                                contract_addr: Addr::unchecked(reward_token_contract.clone()),
+                            },
+                            whitelist: Addr::unchecked(ADMIN.to_string()),
+                        }],
+                        start_time: current_block_time + 10,
+                        end_time: current_block_time + 110,
+                        phases_limit_per_user: None,
+                        max_total_staked: None,
+                        lock_tiers: vec![],
+                        farm_owner: Addr::unchecked(ADMIN.to_string()),
+                    },
+                    &[],
+                    "instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // Execute add reward balance by ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(reward_token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: halo_farm_contract_addr.to_string(),
+                    amount: Uint128::from(100 * GRIEFING_REWARD_PER_SECOND),
+                    msg: to_binary(&FarmCw20HookMsg::AddRewardBalance { phase_index: 0 }).unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let deposit = |app: &mut App, depositor: &str, amount: u128| {
+                app.execute_contract(
+                    Addr::unchecked(depositor.to_string()),
+                    Addr::unchecked(lp_token_contract.clone()),
+                    &Cw20ExecuteMsg::Send {
+                        contract: halo_farm_contract_addr.to_string(),
+                        amount: Uint128::from(amount),
+                        msg: to_binary(&FarmCw20HookMsg::Deposit {
+                            lock_duration: None,
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                )
+            };
+
+            // Execute deposit 1 lp token unit by USER_1
+            assert!(deposit(&mut app, USER_1, 1).is_ok());
+
+            // change block time to 1 second after the start time
+            app.set_block(BlockInfo {
+                time: Timestamp::from_seconds(current_block_time + 11),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute deposit LARGE_STAKED_AMOUNT lp token by ADMIN
+            assert!(deposit(&mut app, ADMIN, LARGE_STAKED_AMOUNT).is_ok());
+
+            // change block time increase 1 second
+            app.set_block(BlockInfo {
+                time: Timestamp::from_seconds(current_block_time + 12),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute harvest by ADMIN and USER_1
+            for harvester in [ADMIN, USER_1] {
+                let response = app.execute_contract(
+                    Addr::unchecked(harvester.to_string()),
+                    halo_farm_contract_addr.clone(),
+                    &FarmExecuteMsg::Harvest { recipient: None },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            let query_reward_token_balance = |app: &App, address: &str| -> u128 {
+                let balance: BalanceResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        reward_token_contract.clone(),
+                        &Cw20QueryMsg::Balance {
+                            address: address.to_string(),
+                        },
+                    )
+                    .unwrap();
+                balance.balance.u128()
+            };
+
+            // USER_1 is paid the first second and its share of the second second
+            let total_staked = LARGE_STAKED_AMOUNT + 1;
+            for (staker, reward, share) in [
+                (
+                    ADMIN,
+                    0,
+                    Uint128::from(GRIEFING_REWARD_PER_SECOND)
+                        .multiply_ratio(LARGE_STAKED_AMOUNT, total_staked)
+                        .u128(),
+                ),
+                (
+                    USER_1,
+                    GRIEFING_REWARD_PER_SECOND,
+                    Uint128::from(GRIEFING_REWARD_PER_SECOND)
+                        .multiply_ratio(1u128, total_staked)
+                        .u128(),
+                ),
+            ] {
+                let paid_reward = query_reward_token_balance(&app, staker) - reward;
+                assert!(paid_reward <= share && paid_reward + 1 >= share);
+            }
         }
     }
 }